@@ -1,65 +1,426 @@
-use chrono::NaiveDate;
+use std::fs;
+use std::num::NonZeroUsize;
 
+use anyhow::{anyhow, bail, Context};
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::domain::base::booking_date::BookingDate;
+use crate::domain::base::cancellation_date::CancellationDate;
+use crate::domain::base::change_date::ChangeDate;
+use crate::domain::base::conventional_line::ConventionalStation;
 use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::group_type::GroupType;
 use crate::domain::base::number_of_passengers::NumberOfPassengers;
+use crate::domain::base::operator::Operator;
+use crate::domain::base::payment_medium::PaymentMedium;
 use crate::domain::base::reserve_type::ReserveType;
 use crate::domain::base::ride_section::{RideSection, Station};
 use crate::domain::base::seat_type::SeatType;
 use crate::domain::base::train::Train;
-use crate::domain::discount::individual_discount::judge_individual_discounts;
-use crate::domain::discount::overall_discount::judge_overall_discount;
-use crate::domain::fare::single_trip_adult_fare::calc_single_trip_adult_fare;
+use crate::domain::base::train_run::TrainRun;
+use crate::domain::change::ticket_change::{calc_ticket_change, Reservation, Settlement};
+use crate::domain::discount::discount_policy::{
+    DiscountMethod, DiscountPolicy, DiscountTarget, RoundingPoint, SingleFareRounding, StackingMode, StackingOverride,
+};
+use crate::domain::discount::overall_discount::GroupFreePassengerPolicy;
+use crate::domain::discount::promotion::Promotion;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::fare_breakdown::{create_fare_breakdown, FareBreakdown};
+use crate::domain::fare::fare_quote::{calc_fare_quote, consume_promotion};
+use crate::domain::fare::operator_revenue::{sum_operator_revenues, OperatorRevenue};
 use crate::domain::fare::total_fare::calc_total_fare;
+use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
+use crate::domain::inventory::inventory_store::{format_seat_inventories, parse_seat_inventories};
+use crate::domain::inventory::seat_allocation::{calc_seat_allocation, SeatAllocation};
+use crate::domain::inventory::seat_inventory::{Seat, SeatInventory};
+use crate::domain::refund::cancellation_refund::{calc_group_refund, IssuedFare, Refund};
+use crate::domain::schedule::timetable::parse_timetable;
+use crate::domain::schedule::train_search::{search_train_runs, ScheduledRun};
+use crate::domain::tariff::tariff_table::parse_tariff_table;
+use crate::domain::tariff::tariff_validation::{validate_tariff_table, TariffViolation};
+use crate::domain::ticket::ticket_issuance::{issue_tickets, Direction, PassengerCategory, Ticket, TicketType};
+use crate::fundamental::amount::Amount;
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::{Rounding, RoundingMode, RoundingUnit};
 
-#[allow(clippy::too_many_arguments)]
 pub fn invoke(
-    departure_input: StationInput,
-    arrival_input: StationInput,
-    train_input: TrainInput,
-    seat_type_input: SeatTypeInput,
-    reserve_type_input: ReserveTypeInput,
+    fare_query_input: FareQueryInput,
+    discount_policy_input: DiscountPolicyInput,
+    promotion_inputs: Vec<PromotionInput>,
+) -> anyhow::Result<QuoteOutput> {
+    // バリデーションされたパラメータをドメインに変換する
+    let discount_policy = discount_policy_input.as_domain()?;
+    let mut promotions = promotion_inputs.iter().map(PromotionInput::as_domain).collect::<Vec<_>>();
+
+    let fare_breakdown = breakdown(&fare_query_input, &discount_policy, &mut promotions)?;
+    Ok(QuoteOutput::from_domain(&fare_breakdown))
+}
+
+// 複数の問い合わせの総料金を会社ごとに按分し、会社ごとに合計する
+// 運賃と特急料金は問い合わせごとの単価のため合計しない
+// 1 件でも料金を算出できない問い合わせがあれば、何件目かを示して中断する
+// プロモーションの利用数は問い合わせの順に消費する
+pub fn apportion_revenues(
+    fare_query_inputs: Vec<FareQueryInput>,
+    discount_policy_input: DiscountPolicyInput,
+    promotion_inputs: Vec<PromotionInput>,
+) -> anyhow::Result<Vec<OperatorTotalFareOutput>> {
+    // バリデーションされたパラメータをドメインに変換する
+    let discount_policy = discount_policy_input.as_domain()?;
+    let mut promotions = promotion_inputs.iter().map(PromotionInput::as_domain).collect::<Vec<_>>();
+
+    let operator_revenues = fare_query_inputs
+        .iter()
+        .enumerate()
+        .map(|(i, fare_query_input)| {
+            breakdown(fare_query_input, &discount_policy, &mut promotions)
+                .map(|fare_breakdown| fare_breakdown.operator_revenues)
+                .with_context(|| format!("{} 件目の問い合わせ", i + 1))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(sum_operator_revenues(&operator_revenues).iter().map(OperatorTotalFareOutput::from_domain).collect())
+}
+
+// 問い合わせの片道おとな料金に割引を適用し、総料金の内訳を作る
+fn breakdown(
+    fare_query_input: &FareQueryInput,
+    discount_policy: &DiscountPolicy,
+    promotions: &mut [Promotion],
+) -> anyhow::Result<FareBreakdown> {
+    let fare_query = fare_query_input.as_domain();
+
+    // 片道おとな料金と適用される割引を求める
+    let quote = calc_fare_quote(&fare_query, discount_policy, &fare_query_input.promotion_code, promotions)?;
+    consume_promotion(promotions, &quote, &fare_query)?;
+
+    // 片道おとな料金に割引を適用して人数分の総料金を算出する
+    let total_fare = calc_total_fare(
+        &fare_query.reserve_type,
+        &fare_query.number_of_passengers,
+        &quote.single_trip_adult_fare,
+        &quote.overall_discount,
+        &quote.individual_discounts,
+        discount_policy,
+    );
+
+    // 総料金の内訳を作る
+    let mut fare_breakdown = create_fare_breakdown(
+        &quote.single_trip_adult_fare,
+        quote.special_fare,
+        &fare_query.ride_section,
+        &quote.regular_express_fares,
+        &quote.overall_discount,
+        &quote.individual_discounts,
+        total_fare,
+    )?;
+    fare_breakdown.notes.extend(quote.notes);
+    Ok(fare_breakdown)
+}
+
+// 発券した場合はプロモーションの利用数を消費し、消費後の利用数を返す
+pub fn issue(
+    fare_query_input: FareQueryInput,
+    discount_policy_input: DiscountPolicyInput,
+    promotion_inputs: Vec<PromotionInput>,
+) -> anyhow::Result<(Vec<TicketOutput>, Vec<PromotionUsageOutput>)> {
+    // バリデーションされたパラメータをドメインに変換する
+    let fare_query = fare_query_input.as_domain();
+    let discount_policy = discount_policy_input.as_domain()?;
+    let mut promotions = promotion_inputs.iter().map(PromotionInput::as_domain).collect::<Vec<_>>();
+
+    // 片道おとな料金と適用される割引を求める
+    let quote = calc_fare_quote(&fare_query, &discount_policy, &fare_query_input.promotion_code, &promotions)?;
+    consume_promotion(&mut promotions, &quote, &fare_query)?;
+
+    // 1 人 1 方向ごとの券に分けて発券する
+    let tickets = issue_tickets(
+        &fare_query,
+        &quote.single_trip_adult_fare,
+        &quote.overall_discount,
+        &quote.individual_discounts,
+        &discount_policy,
+    );
+
+    Ok((
+        tickets.iter().map(TicketOutput::from_domain).collect(),
+        promotions.iter().map(PromotionUsageOutput::from_domain).collect(),
+    ))
+}
+
+pub fn refund(
+    issued_fare_inputs: Vec<IssuedFareInput>,
     y: i32,
     m: u32,
     d: u32,
-    adult: usize,
-    child: usize,
-) -> u64 {
+    cancellation_y: i32,
+    cancellation_m: u32,
+    cancellation_d: u32,
+) -> Option<(Vec<RefundOutput>, RefundOutput)> {
     // バリデーションされたパラメータをドメインに変換する
-    let ride_section = RideSection { departure: departure_input.as_domain(), arrival: arrival_input.as_domain() };
-    let train = train_input.as_domain();
-    let seat_type = seat_type_input.as_domain();
-    let reserve_type = reserve_type_input.as_domain();
+    let issued_fares = issued_fare_inputs.iter().map(IssuedFareInput::as_domain).collect::<Vec<_>>();
     let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(y, m, d).unwrap() };
-    let number_of_passengers = NumberOfPassengers { adult, child };
+    let cancellation_date =
+        CancellationDate { value: NaiveDate::from_ymd_opt(cancellation_y, cancellation_m, cancellation_d).unwrap() };
 
-    // 片道おとな料金を算出する
-    let single_trip_adult_fare = calc_single_trip_adult_fare(&ride_section, &train, &seat_type, &departure_date);
+    // 1 人ずつの払戻額と団体全体の払戻額を算出する
+    let group_refund = calc_group_refund(&issued_fares, &departure_date, &cancellation_date)?;
 
-    // 適用される全体割引を判定する
-    let overall_discount = judge_overall_discount(&number_of_passengers);
+    Some((
+        group_refund.passengers.iter().map(RefundOutput::from_domain).collect(),
+        RefundOutput::from_domain(&group_refund.total),
+    ))
+}
 
-    // 適用される個別割引を判定する
-    let individual_discounts =
-        judge_individual_discounts(&ride_section, &number_of_passengers, departure_date.get_season());
+pub fn change(
+    current_input: ReservationInput,
+    requested_input: ReservationInput,
+    previous_change_count: usize,
+    change_y: i32,
+    change_m: u32,
+    change_d: u32,
+) -> anyhow::Result<Option<ChangeOutput>> {
+    // バリデーションされたパラメータをドメインに変換する
+    let current = current_input.as_domain();
+    let requested = requested_input.as_domain();
+    let change_date = ChangeDate { value: NaiveDate::from_ymd_opt(change_y, change_m, change_d).unwrap() };
 
-    // 片道おとな料金に割引を適用して人数分の総料金を算出する
-    calc_total_fare(
-        &reserve_type,
-        &number_of_passengers,
-        &single_trip_adult_fare,
-        &overall_discount,
-        &individual_discounts,
+    // 変更前後の片道おとな料金から精算額を算出する
+    let Some(ticket_change) = calc_ticket_change(&current, &requested, previous_change_count, &change_date)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(ChangeOutput {
+        handling_fee: ticket_change.handling_fee.value,
+        settlement: match ticket_change.settlement {
+            Settlement::Payment(amount) => SettlementOutput::Payment(amount.value),
+            Settlement::Refund(amount) => SettlementOutput::Refund(amount.value),
+            Settlement::Even => SettlementOutput::Even,
+        },
+    }))
+}
+
+pub fn validate_tariff(path: &str) -> anyhow::Result<Vec<TariffViolation>> {
+    let text = fs::read_to_string(path)?;
+    let tariff_table = parse_tariff_table(&text)?;
+    Ok(validate_tariff_table(&tariff_table))
+}
+
+// 時刻表から乗車日に指定時刻以降に発車して発駅と着駅の両方に停車する列車を探し、列車ごとに総料金を算出する
+// 問い合わせの列車の種類は用いず、見つかった列車の種類で算出する
+pub fn search_trains(
+    path: &str,
+    fare_query_input: FareQueryInput,
+    discount_policy_input: DiscountPolicyInput,
+    promotion_inputs: Vec<PromotionInput>,
+    hour: u32,
+    minute: u32,
+) -> anyhow::Result<Vec<TrainRunOutput>> {
+    // バリデーションされたパラメータをドメインに変換する
+    let fare_query = fare_query_input.as_domain();
+    let discount_policy = discount_policy_input.as_domain()?;
+    let promotions = promotion_inputs.iter().map(PromotionInput::as_domain).collect::<Vec<_>>();
+    let earliest_departure_time =
+        NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(|| anyhow!("時刻が不正です ( {hour}:{minute:02} )"))?;
+
+    let timetable = parse_timetable(&fs::read_to_string(path)?)?;
+    let runs =
+        search_train_runs(&timetable, &fare_query.ride_section, &fare_query.departure_date, &earliest_departure_time);
+
+    Ok(runs
+        .iter()
+        .map(|run| {
+            let fare_query = FareQuery { train: run.train_run.train, ..fare_query };
+            // 利用できない座席の列車も候補から外さず、理由を示す
+            let total_fare =
+                calc_fare_quote(&fare_query, &discount_policy, &fare_query_input.promotion_code, &promotions)
+                    .map(|quote| {
+                        calc_total_fare(
+                            &fare_query.reserve_type,
+                            &fare_query.number_of_passengers,
+                            &quote.single_trip_adult_fare,
+                            &quote.overall_discount,
+                            &quote.individual_discounts,
+                            &discount_policy,
+                        )
+                        .value
+                    })
+                    .map_err(|error| format!("{error:#}"));
+            TrainRunOutput::from_domain(run, total_fare)
+        })
+        .collect())
+}
+
+// 問い合わせの人数分の指定席を在庫ファイルから確保できるかを、在庫ファイルを変更せずに確認する
+// 1 本の列車に乗り切れない場合は、同じ日の同じ種類の列車に分ける案を返す
+pub fn propose_seats(path: &str, fare_query_input: FareQueryInput) -> anyhow::Result<Vec<SeatProposalOutput>> {
+    // バリデーションされたパラメータをドメインに変換する
+    let fare_query = fare_query_input.as_domain();
+
+    let inventories = parse_seat_inventories(&fs::read_to_string(path)?)?;
+    let allocations = allocate_seats(&inventories, &fare_query)?;
+
+    allocations
+        .iter()
+        .map(|allocation| {
+            let inventory = inventories
+                .iter()
+                .find(|inventory| inventory.train_run == allocation.train_run)
+                .ok_or_else(|| missing_inventory(&allocation.train_run))?;
+            Ok(SeatProposalOutput::from_domain(allocation, inventory))
+        })
+        .collect()
+}
+
+// 問い合わせの人数分の指定席を在庫ファイルから確保して予約済みにし、在庫ファイルを更新する
+// 1 本の列車に乗り切れない場合は同じ日の同じ種類の列車に分けて確保する
+pub fn reserve_seats(path: &str, fare_query_input: FareQueryInput) -> anyhow::Result<Vec<SeatReservationOutput>> {
+    // バリデーションされたパラメータをドメインに変換する
+    let fare_query = fare_query_input.as_domain();
+
+    let mut inventories = parse_seat_inventories(&fs::read_to_string(path)?)?;
+    let allocations = allocate_seats(&inventories, &fare_query)?;
+
+    // 割り当てたすべての列車で座席を確保できてから予約済みにする
+    // 途中で確保できなかった場合は確保済みの座席を空席に戻す
+    let mut held_seats: Vec<(TrainRun, Vec<Seat>)> = vec![];
+    for allocation in &allocations {
+        match find_inventory(&mut inventories, &allocation.train_run)?.hold(allocation.count) {
+            Ok(seats) => held_seats.push((allocation.train_run, seats)),
+            Err(error) => {
+                for (train_run, seats) in &held_seats {
+                    find_inventory(&mut inventories, train_run)?
+                        .release(seats)
+                        .map_err(|error| anyhow!(error.describe()))?;
+                }
+                bail!(error.describe());
+            }
+        }
+    }
+    for (train_run, seats) in &held_seats {
+        find_inventory(&mut inventories, train_run)?.confirm(seats).map_err(|error| anyhow!(error.describe()))?;
+    }
+
+    fs::write(path, format_seat_inventories(&inventories))?;
+
+    held_seats
+        .iter()
+        .map(|(train_run, seats)| {
+            Ok(SeatReservationOutput::from_domain(train_run, find_inventory(&mut inventories, train_run)?, seats))
+        })
+        .collect()
+}
+
+// 予約した指定席を在庫ファイルで空席に戻し、在庫ファイルを更新する
+// 空席に戻せない座席が含まれる場合は 1 席も変更しない
+pub fn release_seats(path: &str, seat_release_input: SeatReleaseInput) -> anyhow::Result<SeatReservationOutput> {
+    // バリデーションされたパラメータをドメインに変換する
+    let (train_run, seats) = seat_release_input.as_domain();
+
+    let mut inventories = parse_seat_inventories(&fs::read_to_string(path)?)?;
+    find_inventory(&mut inventories, &train_run)?.release(&seats).map_err(|error| anyhow!(error.describe()))?;
+
+    fs::write(path, format_seat_inventories(&inventories))?;
+
+    Ok(SeatReservationOutput::from_domain(&train_run, find_inventory(&mut inventories, &train_run)?, &seats))
+}
+
+// 人数分の指定席を、乗車日に運転される同じ種類の列車に割り当てる
+fn allocate_seats(inventories: &[SeatInventory], fare_query: &FareQuery) -> anyhow::Result<Vec<SeatAllocation>> {
+    if fare_query.seat_type == SeatType::Free {
+        bail!("自由席は座席を確保しません");
+    }
+
+    let candidates = inventories
+        .iter()
+        .filter(|inventory| {
+            inventory.train_run.train == fare_query.train
+                && inventory.train_run.departure_date == fare_query.departure_date
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let count = fare_query.number_of_passengers.total();
+    calc_seat_allocation(&candidates, count).ok_or_else(|| anyhow!("{count} 名分の指定席がありません"))
+}
+
+fn find_inventory<'a>(
+    inventories: &'a mut [SeatInventory],
+    train_run: &TrainRun,
+) -> anyhow::Result<&'a mut SeatInventory> {
+    inventories
+        .iter_mut()
+        .find(|inventory| inventory.train_run == *train_run)
+        .ok_or_else(|| missing_inventory(train_run))
+}
+
+fn missing_inventory(train_run: &TrainRun) -> anyhow::Error {
+    anyhow!(
+        "{} {} 号 ( {} ) の在庫がありません",
+        train_run.train.describe(),
+        train_run.number,
+        train_run.departure_date.value.format("%Y-%m-%d")
     )
-    .value
 }
 
 // presentation で domain に関与しないまま安全にバリデーション結果を受け渡すための enum
 
+pub struct FareQueryInput {
+    pub departure: StationInput,
+    pub arrival: StationInput,
+    pub train: TrainInput,
+    pub seat_type: SeatTypeInput,
+    pub reserve_type: ReserveTypeInput,
+    pub y: i32,
+    pub m: u32,
+    pub d: u32,
+    pub adult: usize,
+    pub child: usize,
+    pub group_type: GroupTypeInput,
+    pub payment_medium: PaymentMediumInput,
+    pub ex_product: Option<ExProductInput>,
+    pub departure_connection: Option<ConventionalStationInput>,
+    pub arrival_connection: Option<ConventionalStationInput>,
+    pub booking_y: i32,
+    pub booking_m: u32,
+    pub booking_d: u32,
+    pub promotion_code: Option<String>,
+}
+
+impl FareQueryInput {
+    fn as_domain(&self) -> FareQuery {
+        FareQuery {
+            ride_section: RideSection {
+                departure: self.departure.as_domain(),
+                arrival: self.arrival.as_domain(),
+                departure_connection: self.departure_connection.as_ref().map(ConventionalStationInput::as_domain),
+                arrival_connection: self.arrival_connection.as_ref().map(ConventionalStationInput::as_domain),
+            },
+            train: self.train.as_domain(),
+            seat_type: self.seat_type.as_domain(),
+            reserve_type: self.reserve_type.as_domain(),
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(self.y, self.m, self.d).unwrap() },
+            number_of_passengers: NumberOfPassengers { adult: self.adult, child: self.child },
+            group_type: self.group_type.as_domain(),
+            payment_medium: self.payment_medium.as_domain(),
+            ex_product: self.ex_product.as_ref().map(ExProductInput::as_domain),
+            booking_date: BookingDate {
+                value: NaiveDate::from_ymd_opt(self.booking_y, self.booking_m, self.booking_d).unwrap(),
+            },
+        }
+    }
+}
+
 pub enum StationInput {
     Tokyo,
     #[allow(dead_code)]
     ShinOsaka,
     Himeji,
+    #[allow(dead_code)]
+    Hakata,
+    #[allow(dead_code)]
+    KagoshimaChuo,
 }
 
 impl StationInput {
@@ -68,6 +429,27 @@ impl StationInput {
             Self::Tokyo => Station::Tokyo,
             Self::ShinOsaka => Station::ShinOsaka,
             Self::Himeji => Station::Himeji,
+            Self::Hakata => Station::Hakata,
+            Self::KagoshimaChuo => Station::KagoshimaChuo,
+        }
+    }
+}
+
+pub enum ConventionalStationInput {
+    #[allow(dead_code)]
+    Shinagawa,
+    #[allow(dead_code)]
+    Kakogawa,
+    #[allow(dead_code)]
+    HonTatsuno,
+}
+
+impl ConventionalStationInput {
+    fn as_domain(&self) -> ConventionalStation {
+        match self {
+            Self::Shinagawa => ConventionalStation::Shinagawa,
+            Self::Kakogawa => ConventionalStation::Kakogawa,
+            Self::HonTatsuno => ConventionalStation::HonTatsuno,
         }
     }
 }
@@ -76,6 +458,10 @@ pub enum TrainInput {
     #[allow(dead_code)]
     Hikari,
     Nozomi,
+    #[allow(dead_code)]
+    Sakura,
+    #[allow(dead_code)]
+    Mizuho,
 }
 
 impl TrainInput {
@@ -83,6 +469,8 @@ impl TrainInput {
         match self {
             Self::Hikari => Train::Hikari,
             Self::Nozomi => Train::Nozomi,
+            Self::Sakura => Train::Sakura,
+            Self::Mizuho => Train::Mizuho,
         }
     }
 }
@@ -103,7 +491,6 @@ impl SeatTypeInput {
 }
 
 pub enum ReserveTypeInput {
-    #[allow(dead_code)]
     SingleTrip,
     RoundTrip,
 }
@@ -116,3 +503,520 @@ impl ReserveTypeInput {
         }
     }
 }
+
+pub enum PaymentMediumInput {
+    Ticket,
+    #[allow(dead_code)]
+    IcCard,
+}
+
+impl PaymentMediumInput {
+    fn as_domain(&self) -> PaymentMedium {
+        match self {
+            Self::Ticket => PaymentMedium::Ticket,
+            Self::IcCard => PaymentMedium::IcCard,
+        }
+    }
+}
+
+pub enum ExProductInput {
+    #[allow(dead_code)]
+    SmartEx,
+    #[allow(dead_code)]
+    ExReservation,
+}
+
+impl ExProductInput {
+    fn as_domain(&self) -> ExProduct {
+        match self {
+            Self::SmartEx => ExProduct::SmartEx,
+            Self::ExReservation => ExProduct::ExReservation,
+        }
+    }
+}
+
+pub enum GroupTypeInput {
+    Ordinary,
+    #[allow(dead_code)]
+    School,
+    #[allow(dead_code)]
+    Visiting,
+}
+
+impl GroupTypeInput {
+    fn as_domain(&self) -> GroupType {
+        match self {
+            Self::Ordinary => GroupType::Ordinary,
+            Self::School => GroupType::School,
+            Self::Visiting => GroupType::Visiting,
+        }
+    }
+}
+
+pub struct DiscountPolicyInput {
+    pub stacking_overrides: Vec<StackingOverrideInput>,
+    pub single_fare_rounding: SingleFareRoundingInput,
+    // 無料になる旅客がいる団体の最少の人数と、1 名が無料になる人数
+    pub group_min_passengers: usize,
+    pub passengers_per_free_passenger: usize,
+}
+
+impl DiscountPolicyInput {
+    // 1 名が無料になる人数に 0 名は指定できない
+    fn as_domain(&self) -> anyhow::Result<DiscountPolicy> {
+        let passengers_per_free_passenger = NonZeroUsize::new(self.passengers_per_free_passenger)
+            .ok_or_else(|| anyhow!("1 名が無料になる人数は 1 名以上を指定してください"))?;
+        Ok(DiscountPolicy {
+            stacking_overrides: self.stacking_overrides.iter().map(StackingOverrideInput::as_domain).collect(),
+            single_fare_rounding: match self.single_fare_rounding {
+                SingleFareRoundingInput::PerComponent => SingleFareRounding::PerComponent,
+                SingleFareRoundingInput::Combined => SingleFareRounding::Combined,
+            },
+            group_free_passenger: GroupFreePassengerPolicy {
+                min_passengers: self.group_min_passengers,
+                passengers_per_free_passenger,
+            },
+        })
+    }
+}
+
+// 割引の名前を指定して、その割引が宣言した重ね方と端数処理のタイミングを置き換える
+pub struct StackingOverrideInput {
+    pub name: String,
+    pub stacking_mode: StackingModeInput,
+    pub rounding_point: RoundingPointInput,
+}
+
+impl StackingOverrideInput {
+    fn as_domain(&self) -> StackingOverride {
+        StackingOverride {
+            name: self.name.clone(),
+            stacking_mode: self.stacking_mode.as_domain(),
+            rounding_point: self.rounding_point.as_domain(),
+        }
+    }
+}
+
+pub enum StackingModeInput {
+    Sequential,
+    Multiplicative,
+}
+
+impl StackingModeInput {
+    fn as_domain(&self) -> StackingMode {
+        match self {
+            Self::Sequential => StackingMode::Sequential,
+            Self::Multiplicative => StackingMode::Multiplicative,
+        }
+    }
+}
+
+pub enum RoundingPointInput {
+    EachStep,
+    Last,
+}
+
+impl RoundingPointInput {
+    fn as_domain(&self) -> RoundingPoint {
+        match self {
+            Self::EachStep => RoundingPoint::EachStep,
+            Self::Last => RoundingPoint::Last,
+        }
+    }
+}
+
+pub enum SingleFareRoundingInput {
+    PerComponent,
+    #[allow(dead_code)]
+    Combined,
+}
+
+pub struct PromotionInput {
+    pub code: String,
+    pub name: String,
+    pub booking_from: (i32, u32, u32),
+    pub booking_to: (i32, u32, u32),
+    pub travel_from: (i32, u32, u32),
+    pub travel_to: (i32, u32, u32),
+    pub station_pairs: Vec<(StationInput, StationInput)>,
+    pub trains: Vec<TrainInput>,
+    pub seat_types: Vec<SeatTypeInput>,
+    pub target: DiscountTargetInput,
+    pub benefit: PromotionBenefitInput,
+    pub rounding_mode: RoundingModeInput,
+    pub rounding_unit: RoundingUnitInput,
+    pub stacking_mode: StackingModeInput,
+    pub rounding_point: RoundingPointInput,
+    pub max_uses: usize,
+    pub used_count: usize,
+}
+
+impl PromotionInput {
+    fn as_domain(&self) -> Promotion {
+        let date = |(y, m, d): (i32, u32, u32)| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        Promotion {
+            code: self.code.clone(),
+            name: self.name.clone(),
+            booking_period: date(self.booking_from)..=date(self.booking_to),
+            travel_period: date(self.travel_from)..=date(self.travel_to),
+            station_pairs: self
+                .station_pairs
+                .iter()
+                .map(|(departure, arrival)| {
+                    let ride_section = RideSection {
+                        departure: departure.as_domain(),
+                        arrival: arrival.as_domain(),
+                        departure_connection: None,
+                        arrival_connection: None,
+                    };
+                    let (departure, arrival) = ride_section.get_station_pair();
+                    (*departure, *arrival)
+                })
+                .collect(),
+            trains: self.trains.iter().map(TrainInput::as_domain).collect(),
+            seat_types: self.seat_types.iter().map(SeatTypeInput::as_domain).collect(),
+            target: match self.target {
+                DiscountTargetInput::TrainFare => DiscountTarget::TrainFare,
+                DiscountTargetInput::ExpressFare => DiscountTarget::ExpressFare,
+                DiscountTargetInput::SingleFare => DiscountTarget::SingleFare,
+            },
+            discount_method: match self.benefit {
+                PromotionBenefitInput::Rate(percent) => DiscountMethod::Rate(Fraction::percent(percent)),
+                PromotionBenefitInput::FixedAmount(amount) => DiscountMethod::FixedAmount(Amount { value: amount }),
+            },
+            rounding: Rounding {
+                mode: match self.rounding_mode {
+                    RoundingModeInput::Floor => RoundingMode::Floor,
+                    RoundingModeInput::Round => RoundingMode::Round,
+                    RoundingModeInput::Ceil => RoundingMode::Ceil,
+                },
+                unit: match self.rounding_unit {
+                    RoundingUnitInput::One => RoundingUnit::One,
+                    RoundingUnitInput::Ten => RoundingUnit::Ten,
+                    RoundingUnitInput::Hundred => RoundingUnit::Hundred,
+                },
+            },
+            stacking_mode: self.stacking_mode.as_domain(),
+            rounding_point: self.rounding_point.as_domain(),
+            max_uses: self.max_uses,
+            used_count: self.used_count,
+        }
+    }
+}
+
+pub enum RoundingModeInput {
+    Floor,
+    #[allow(dead_code)]
+    Round,
+    #[allow(dead_code)]
+    Ceil,
+}
+
+pub enum RoundingUnitInput {
+    #[allow(dead_code)]
+    One,
+    Ten,
+    #[allow(dead_code)]
+    Hundred,
+}
+
+#[allow(clippy::enum_variant_names)]
+pub enum DiscountTargetInput {
+    #[allow(dead_code)]
+    TrainFare,
+    ExpressFare,
+    #[allow(dead_code)]
+    SingleFare,
+}
+
+pub enum PromotionBenefitInput {
+    // 割り引いた後の割合 ( 百分率 )
+    #[allow(dead_code)]
+    Rate(u64),
+    FixedAmount(u64),
+}
+
+pub struct IssuedFareInput {
+    pub train_fare: u64,
+    pub express_fare: u64,
+    pub seat_type: SeatTypeInput,
+    pub ex_product: Option<ExProductInput>,
+}
+
+impl IssuedFareInput {
+    fn as_domain(&self) -> IssuedFare {
+        IssuedFare {
+            train_fare: TrainFare { value: Amount { value: self.train_fare } },
+            express_fare: ExpressFare { value: Amount { value: self.express_fare } },
+            seat_type: self.seat_type.as_domain(),
+            ex_product: self.ex_product.as_ref().map(ExProductInput::as_domain),
+        }
+    }
+}
+
+pub struct SeatReleaseInput {
+    pub train: TrainInput,
+    pub number: u32,
+    pub y: i32,
+    pub m: u32,
+    pub d: u32,
+    // 号車、列番号、座席記号 ( 1 号車 1A なら (1, 1, 'A') )
+    pub seats: Vec<(u32, u32, char)>,
+}
+
+impl SeatReleaseInput {
+    fn as_domain(&self) -> (TrainRun, Vec<Seat>) {
+        (
+            TrainRun {
+                train: self.train.as_domain(),
+                number: self.number,
+                departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(self.y, self.m, self.d).unwrap() },
+            },
+            self.seats.iter().map(|(car, row, column)| Seat { car: *car, row: *row, column: *column }).collect(),
+        )
+    }
+}
+
+pub struct ReservationInput {
+    pub departure: StationInput,
+    pub arrival: StationInput,
+    pub train: TrainInput,
+    pub seat_type: SeatTypeInput,
+    pub y: i32,
+    pub m: u32,
+    pub d: u32,
+    pub ex_product: Option<ExProductInput>,
+}
+
+impl ReservationInput {
+    fn as_domain(&self) -> Reservation {
+        Reservation {
+            ride_section: RideSection {
+                departure: self.departure.as_domain(),
+                arrival: self.arrival.as_domain(),
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train: self.train.as_domain(),
+            seat_type: self.seat_type.as_domain(),
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(self.y, self.m, self.d).unwrap() },
+            ex_product: self.ex_product.as_ref().map(ExProductInput::as_domain),
+        }
+    }
+}
+
+// domain を presentation に漏らさないための出力
+
+pub struct QuoteOutput {
+    pub train_fare: u64,
+    pub train_fare_type: &'static str,
+    pub special_train_fare: bool,
+    pub express_fare: u64,
+    pub special_express_fare: bool,
+    pub validity_days: u32,
+    pub stopover: bool,
+    pub operator_revenues: Vec<OperatorRevenueOutput>,
+    pub discounts: Vec<String>,
+    pub notes: Vec<String>,
+    pub total_fare: u64,
+}
+
+impl QuoteOutput {
+    fn from_domain(fare_breakdown: &FareBreakdown) -> Self {
+        Self {
+            train_fare: fare_breakdown.train_fare.value.value,
+            train_fare_type: match fare_breakdown.train_fare_type {
+                TrainFareType::Ticket => "きっぷ",
+                TrainFareType::Ic => "IC",
+            },
+            special_train_fare: fare_breakdown.special_fare.train_fare,
+            express_fare: fare_breakdown.express_fare.value.value,
+            special_express_fare: fare_breakdown.special_fare.express_fare,
+            validity_days: fare_breakdown.validity_period.days,
+            stopover: fare_breakdown.validity_period.stopover,
+            operator_revenues: fare_breakdown
+                .operator_revenues
+                .iter()
+                .map(OperatorRevenueOutput::from_domain)
+                .collect(),
+            discounts: fare_breakdown.discounts.clone(),
+            notes: fare_breakdown.notes.clone(),
+            total_fare: fare_breakdown.total_fare.value,
+        }
+    }
+}
+
+pub struct PromotionUsageOutput {
+    pub code: String,
+    pub used_count: usize,
+    pub max_uses: usize,
+}
+
+impl PromotionUsageOutput {
+    fn from_domain(promotion: &Promotion) -> Self {
+        Self { code: promotion.code.clone(), used_count: promotion.used_count, max_uses: promotion.max_uses }
+    }
+}
+
+pub struct OperatorRevenueOutput {
+    pub operator: &'static str,
+    pub train_fare: u64,
+    pub express_fare: u64,
+    pub total_fare: u64,
+}
+
+impl OperatorRevenueOutput {
+    fn from_domain(operator_revenue: &OperatorRevenue) -> Self {
+        Self {
+            operator: operator_revenue.operator.describe(),
+            train_fare: operator_revenue.train_fare.value.value,
+            express_fare: operator_revenue.express_fare.value.value,
+            total_fare: operator_revenue.total_fare.value,
+        }
+    }
+}
+
+pub struct OperatorTotalFareOutput {
+    pub operator: &'static str,
+    pub total_fare: u64,
+}
+
+impl OperatorTotalFareOutput {
+    fn from_domain((operator, total_fare): &(Operator, Amount)) -> Self {
+        Self { operator: operator.describe(), total_fare: total_fare.value }
+    }
+}
+
+pub struct RefundOutput {
+    pub handling_fee: u64,
+    pub refundable_amount: u64,
+}
+
+impl RefundOutput {
+    fn from_domain(refund: &Refund) -> Self {
+        Self { handling_fee: refund.handling_fee.value, refundable_amount: refund.refundable_amount.value }
+    }
+}
+
+pub struct ChangeOutput {
+    pub handling_fee: u64,
+    pub settlement: SettlementOutput,
+}
+
+pub enum SettlementOutput {
+    Payment(u64),
+    Refund(u64),
+    Even,
+}
+
+pub struct TicketOutput {
+    pub ticket_type: &'static str,
+    pub passenger_category: &'static str,
+    pub direction: &'static str,
+    pub departure: &'static str,
+    pub arrival: &'static str,
+    pub departure_date: String,
+    pub train: Option<&'static str>,
+    pub seat_type: Option<&'static str>,
+    pub validity_days: Option<u32>,
+    pub stopover: Option<bool>,
+    pub price: u64,
+}
+
+impl TicketOutput {
+    fn from_domain(ticket: &Ticket) -> Self {
+        Self {
+            ticket_type: match ticket.ticket_type {
+                TicketType::TrainTicket => "乗車券",
+                TicketType::ExpressTicket => "特急券",
+            },
+            passenger_category: match ticket.passenger_category {
+                PassengerCategory::Adult => "おとな",
+                PassengerCategory::Child => "こども",
+            },
+            direction: match ticket.direction {
+                Direction::Outbound => "往路",
+                Direction::Return => "復路",
+            },
+            // 特定都区市内を適用した乗車券は駅名の代わりに区域名を、在来線で乗り継ぐ場合は在来線の駅名を記載する
+            departure: ticket.city_zones.0.map_or(
+                endpoint_name(&ticket.ride_section.departure, &ticket.ride_section.departure_connection),
+                |city_zone| city_zone.describe(),
+            ),
+            arrival: ticket.city_zones.1.map_or(
+                endpoint_name(&ticket.ride_section.arrival, &ticket.ride_section.arrival_connection),
+                |city_zone| city_zone.describe(),
+            ),
+            departure_date: ticket.departure_date.value.format("%Y-%m-%d").to_string(),
+            train: ticket.train.as_ref().map(Train::describe),
+            seat_type: ticket.seat_type.as_ref().map(SeatType::describe),
+            validity_days: ticket.validity_period.map(|validity_period| validity_period.days),
+            stopover: ticket.validity_period.map(|validity_period| validity_period.stopover),
+            price: ticket.price.value,
+        }
+    }
+}
+
+pub struct TrainRunOutput {
+    pub train: &'static str,
+    pub number: u32,
+    pub departure_time: String,
+    pub arrival_time: String,
+    pub total_fare: Result<u64, String>,
+}
+
+impl TrainRunOutput {
+    fn from_domain(run: &ScheduledRun, total_fare: Result<u64, String>) -> Self {
+        Self {
+            train: run.train_run.train.describe(),
+            number: run.train_run.number,
+            departure_time: run.departure_time.format("%H:%M").to_string(),
+            arrival_time: run.arrival_time.format("%H:%M").to_string(),
+            total_fare,
+        }
+    }
+}
+
+pub struct SeatProposalOutput {
+    pub train: &'static str,
+    pub number: u32,
+    pub departure_date: String,
+    pub available_count: usize,
+    pub count: usize,
+}
+
+impl SeatProposalOutput {
+    fn from_domain(allocation: &SeatAllocation, inventory: &SeatInventory) -> Self {
+        Self {
+            train: allocation.train_run.train.describe(),
+            number: allocation.train_run.number,
+            departure_date: allocation.train_run.departure_date.value.format("%Y-%m-%d").to_string(),
+            available_count: inventory.get_available_count(),
+            count: allocation.count,
+        }
+    }
+}
+
+pub struct SeatReservationOutput {
+    pub train: &'static str,
+    pub number: u32,
+    pub departure_date: String,
+    pub reserved_capacity: usize,
+    pub seats: Vec<String>,
+}
+
+impl SeatReservationOutput {
+    fn from_domain(train_run: &TrainRun, inventory: &SeatInventory, seats: &[Seat]) -> Self {
+        Self {
+            train: train_run.train.describe(),
+            number: train_run.number,
+            departure_date: train_run.departure_date.value.format("%Y-%m-%d").to_string(),
+            reserved_capacity: inventory.get_capacity(&SeatType::Reserved),
+            seats: seats.iter().map(Seat::describe).collect(),
+        }
+    }
+}
+
+fn endpoint_name(station: &Station, connection: &Option<ConventionalStation>) -> &'static str {
+    connection.map_or(station.describe(), |connection| connection.describe())
+}
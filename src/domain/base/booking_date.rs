@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+
+use crate::domain::base::departure_date::DepartureDate;
+
+// 予約 ( 購入 ) した日
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct BookingDate {
+    pub value: NaiveDate,
+}
+
+impl BookingDate {
+    // 出発日の何日前に予約したか
+    pub fn get_lead_days(&self, departure_date: &DepartureDate) -> i64 {
+        (departure_date.value - self.value).num_days()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::booking_date::BookingDate;
+    use crate::domain::base::departure_date::DepartureDate;
+
+    #[rstest]
+    #[case(12, 7, 21)]
+    #[case(12, 28, 0)]
+    fn get_lead_days(#[case] m: u32, #[case] d: u32, #[case] exp: i64) {
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() };
+        let sut = BookingDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
+        assert_eq!(exp, sut.get_lead_days(&departure_date));
+    }
+}
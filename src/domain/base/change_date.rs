@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+
+use crate::domain::base::departure_date::DepartureDate;
+
+pub struct ChangeDate {
+    pub value: NaiveDate,
+}
+
+impl ChangeDate {
+    // 出発日の何日前か ( 出発日当日は 0 、出発日を過ぎていれば負 )
+    pub fn get_days_before(&self, departure_date: &DepartureDate) -> i64 {
+        (departure_date.value - self.value).num_days()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::change_date::ChangeDate;
+    use crate::domain::base::departure_date::DepartureDate;
+
+    #[rstest]
+    #[case(12, 26, 2)]
+    #[case(12, 28, 0)]
+    #[case(12, 29, -1)]
+    fn get_days_before(#[case] m: u32, #[case] d: u32, #[case] exp: i64) {
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() };
+        let sut = ChangeDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
+        assert_eq!(exp, sut.get_days_before(&departure_date));
+    }
+}
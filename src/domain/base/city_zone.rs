@@ -0,0 +1,49 @@
+use crate::domain::base::ride_section::Station;
+
+// 特定都区市内
+// 中心駅からの営業キロが 200 km を超える場合、区域内のどの駅も中心駅として運賃を算出する
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum CityZone {
+    // 東京都区内 ( 中心駅は東京 )
+    TokyoWards,
+    // 大阪市内 ( 中心駅は大阪 )
+    OsakaCity,
+}
+
+impl CityZone {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::TokyoWards => "東京都区内",
+            Self::OsakaCity => "大阪市内",
+        }
+    }
+}
+
+// 特定都区市内の駅と、中心駅からの営業キロ
+// 営業キロは東京から離れる向きを正とする ( 新大阪は大阪より東京寄り )
+pub struct CityZoneMember {
+    pub station: Station,
+    pub city_zone: CityZone,
+    pub kilometer_from_central: f64,
+}
+
+pub fn get_city_zone_members() -> Vec<CityZoneMember> {
+    vec![
+        CityZoneMember { station: Station::Tokyo, city_zone: CityZone::TokyoWards, kilometer_from_central: 0.0 },
+        CityZoneMember { station: Station::ShinOsaka, city_zone: CityZone::OsakaCity, kilometer_from_central: -3.8 },
+    ]
+}
+
+// 特定都区市内の運賃計算に用いる、中心駅からの距離の下限
+pub const CITY_ZONE_MIN_KILOMETER: f64 = 200.0;
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::base::city_zone::CityZone;
+
+    #[test]
+    fn describe() {
+        assert_eq!("東京都区内", CityZone::TokyoWards.describe());
+        assert_eq!("大阪市内", CityZone::OsakaCity.describe());
+    }
+}
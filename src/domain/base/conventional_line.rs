@@ -0,0 +1,109 @@
+use crate::domain::base::city_zone::CityZone;
+use crate::domain::base::operator::Operator;
+use crate::domain::base::ride_section::Station;
+use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+// 新幹線の駅から在来線で乗り継ぐ駅
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ConventionalStation {
+    // 東海道本線で東京から
+    Shinagawa,
+    // 山陽本線で姫路から
+    Kakogawa,
+    // 姫新線で姫路から
+    HonTatsuno,
+}
+
+impl ConventionalStation {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Shinagawa => "品川",
+            Self::Kakogawa => "加古川",
+            Self::HonTatsuno => "本竜野",
+        }
+    }
+}
+
+// 線区の種類
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum LineType {
+    // 幹線
+    Trunk,
+    // 地方交通線
+    Local,
+}
+
+// 新幹線の駅と在来線の駅を結ぶ区間
+// 特定都区市内の駅は、乗り継ぐ新幹線の駅と同じ区域に限る
+pub struct ConventionalSegment {
+    pub station: ConventionalStation,
+    pub connecting_station: Station,
+    pub line_type: LineType,
+    pub operator: Operator,
+    pub operating_kilometer: OperatingKilometer,
+    pub city_zone: Option<CityZone>,
+}
+
+impl ConventionalSegment {
+    // 幹線と乗り継ぐ地方交通線は営業キロを 1.1 倍した換算キロ ( 0.1 km 未満切り上げ ) で運賃を算出する
+    // 擬制キロを用いる JR 九州の地方交通線は、運賃の計算で乗り継ぎごと拒否する ( train_fare::calc_train_fare_by_medium )
+    pub fn get_fare_kilometer(&self) -> f64 {
+        match self.line_type {
+            LineType::Trunk => self.operating_kilometer.value,
+            LineType::Local => (self.operating_kilometer.value * 11.0).ceil() / 10.0,
+        }
+    }
+}
+
+pub fn get_conventional_segments() -> Vec<ConventionalSegment> {
+    vec![
+        ConventionalSegment {
+            station: ConventionalStation::Shinagawa,
+            connecting_station: Station::Tokyo,
+            line_type: LineType::Trunk,
+            operator: Operator::East,
+            operating_kilometer: OperatingKilometer { value: 6.8 },
+            city_zone: Some(CityZone::TokyoWards),
+        },
+        ConventionalSegment {
+            station: ConventionalStation::Kakogawa,
+            connecting_station: Station::Himeji,
+            line_type: LineType::Trunk,
+            operator: Operator::West,
+            operating_kilometer: OperatingKilometer { value: 14.2 },
+            city_zone: None,
+        },
+        ConventionalSegment {
+            station: ConventionalStation::HonTatsuno,
+            connecting_station: Station::Himeji,
+            line_type: LineType::Local,
+            operator: Operator::West,
+            operating_kilometer: OperatingKilometer { value: 15.6 },
+            city_zone: None,
+        },
+    ]
+}
+
+pub fn find_conventional_segment(station: &ConventionalStation) -> ConventionalSegment {
+    get_conventional_segments()
+        .into_iter()
+        .find(|segment| segment.station == *station)
+        .unwrap_or_else(|| panic!("unexpected conventional station"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::conventional_line::ConventionalStation::*;
+    use crate::domain::base::conventional_line::{find_conventional_segment, ConventionalStation};
+
+    #[rstest]
+    #[case(Shinagawa, 6.8)]
+    #[case(Kakogawa, 14.2)]
+    // 地方交通線は換算キロ
+    #[case(HonTatsuno, 17.2)]
+    fn fare_kilometer(#[case] station: ConventionalStation, #[case] exp: f64) {
+        assert_eq!(exp, find_conventional_segment(&station).get_fare_kilometer());
+    }
+}
@@ -2,6 +2,7 @@ use chrono::{Datelike, NaiveDate};
 
 use crate::domain::base::departure_date::Season::{OffPeak, Peak, Regular};
 
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub struct DepartureDate {
     pub value: NaiveDate,
 }
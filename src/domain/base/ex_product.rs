@@ -0,0 +1,18 @@
+// EX サービスで予約する場合の商品
+// 運賃はきっぷと同じで、特急料金と払戻手数料が通常のきっぷと異なる
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ExProduct {
+    // スマートEX
+    SmartEx,
+    // EX予約 ( 会員価格 )
+    ExReservation,
+}
+
+impl ExProduct {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::SmartEx => "スマートEX",
+            Self::ExReservation => "EX予約",
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::base::booking_date::BookingDate;
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::group_type::GroupType;
+use crate::domain::base::number_of_passengers::NumberOfPassengers;
+use crate::domain::base::payment_medium::PaymentMedium;
+use crate::domain::base::reserve_type::ReserveType;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+
+// 料金を問い合わせる際の条件一式
+pub struct FareQuery {
+    pub ride_section: RideSection,
+    pub train: Train,
+    pub seat_type: SeatType,
+    pub reserve_type: ReserveType,
+    pub departure_date: DepartureDate,
+    pub number_of_passengers: NumberOfPassengers,
+    pub group_type: GroupType,
+    pub payment_medium: PaymentMedium,
+    pub ex_product: Option<ExProduct>,
+    pub booking_date: BookingDate,
+}
+
+// テスト用の既定の条件 ( 東京 → 新大阪 のぞみ 指定席 片道 大人 1 名 、通常期の 2 月 22 日に乗車 )
+// 各テストでは必要な項目だけを上書きする
+#[cfg(test)]
+impl Default for FareQuery {
+    fn default() -> Self {
+        use chrono::NaiveDate;
+
+        use crate::domain::base::ride_section::Station;
+
+        FareQuery {
+            ride_section: RideSection {
+                departure: Station::Tokyo,
+                arrival: Station::ShinOsaka,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train: Train::Nozomi,
+            seat_type: SeatType::Reserved,
+            reserve_type: ReserveType::SingleTrip,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 2, 22).unwrap() },
+            number_of_passengers: NumberOfPassengers { adult: 1, child: 0 },
+            group_type: GroupType::Ordinary,
+            payment_medium: PaymentMedium::Ticket,
+            ex_product: None,
+            booking_date: BookingDate { value: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() },
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// 8 名以上の団体の種類
+// 団体割引の割引率と無料になる人数は種類ごとに異なる
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum GroupType {
+    // 普通団体
+    Ordinary,
+    // 学生団体 ( おとなは学生と付添の教職員 )
+    School,
+    // 訪日観光団体
+    Visiting,
+}
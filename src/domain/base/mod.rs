@@ -1,6 +1,19 @@
+pub mod booking_date;
+pub mod cancellation_date;
+pub mod change_date;
+pub mod city_zone;
+pub mod conventional_line;
 pub mod departure_date;
+pub mod ex_product;
+pub mod fare_query;
+pub mod group_type;
 pub mod number_of_passengers;
+pub mod operator;
+pub mod payment_medium;
 pub mod reserve_type;
 pub mod ride_section;
 pub mod seat_type;
+pub mod shinkansen_line;
+pub mod stop_pattern;
 pub mod train;
+pub mod train_run;
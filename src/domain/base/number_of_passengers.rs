@@ -1,3 +1,4 @@
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub struct NumberOfPassengers {
     pub adult: usize,
     pub child: usize,
@@ -0,0 +1,32 @@
+// 新幹線と在来線を運行する会社 ( 東から順に並べる )
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Operator {
+    East,
+    Central,
+    West,
+    Kyushu,
+}
+
+impl Operator {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::East => "JR東日本",
+            Self::Central => "JR東海",
+            Self::West => "JR西日本",
+            Self::Kyushu => "JR九州",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::base::operator::Operator;
+
+    #[test]
+    fn describe() {
+        assert_eq!("JR東日本", Operator::East.describe());
+        assert_eq!("JR東海", Operator::Central.describe());
+        assert_eq!("JR西日本", Operator::West.describe());
+        assert_eq!("JR九州", Operator::Kyushu.describe());
+    }
+}
@@ -0,0 +1,10 @@
+// 運賃と料金の支払い方法
+// 区間によってはきっぷと IC カードで運賃が異なる
+// EX サービスで予約するかどうかは支払い方法ではなく FareQuery の ex_product で表す
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum PaymentMedium {
+    // 紙のきっぷ
+    Ticket,
+    // 交通系 IC カード
+    IcCard,
+}
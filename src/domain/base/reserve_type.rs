@@ -1,3 +1,4 @@
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub enum ReserveType {
     SingleTrip,
     RoundTrip,
@@ -1,13 +1,22 @@
+use anyhow::anyhow;
+
+use crate::domain::base::city_zone::{get_city_zone_members, CityZone, CityZoneMember, CITY_ZONE_MIN_KILOMETER};
+use crate::domain::base::conventional_line::{find_conventional_segment, ConventionalStation};
 use crate::fundamental::operating_kilometer::OperatingKilometer;
 
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub struct RideSection {
     pub departure: Station,
     pub arrival: Station,
+    // 新幹線の発駅の前と着駅の後に在来線で乗り継ぐ駅
+    // 特急料金は新幹線の区間だけに、運賃は在来線を含む全体にかかる
+    pub departure_connection: Option<ConventionalStation>,
+    pub arrival_connection: Option<ConventionalStation>,
 }
 
 impl RideSection {
     pub fn get_station_pair(&self) -> (&Station, &Station) {
-        let mut stations = vec![&self.departure, &self.arrival];
+        let mut stations = [&self.departure, &self.arrival];
         stations.sort();
 
         (stations[0], stations[1])
@@ -17,24 +26,158 @@ impl RideSection {
         match self.get_station_pair() {
             (Station::Tokyo, Station::ShinOsaka) => &OperatingKilometer { value: 553.0 },
             (Station::Tokyo, Station::Himeji) => &OperatingKilometer { value: 644.0 },
+            (Station::Tokyo, Station::Hakata) => &OperatingKilometer { value: 1174.9 },
+            (Station::ShinOsaka, Station::Himeji) => &OperatingKilometer { value: 91.0 },
+            (Station::ShinOsaka, Station::Hakata) => &OperatingKilometer { value: 621.9 },
+            (Station::Himeji, Station::Hakata) => &OperatingKilometer { value: 530.9 },
+            (Station::Tokyo, Station::KagoshimaChuo) => &OperatingKilometer { value: 1463.8 },
+            (Station::ShinOsaka, Station::KagoshimaChuo) => &OperatingKilometer { value: 910.8 },
+            (Station::Himeji, Station::KagoshimaChuo) => &OperatingKilometer { value: 819.8 },
+            (Station::Hakata, Station::KagoshimaChuo) => &OperatingKilometer { value: 288.9 },
             _ => panic!("unexpected ride section"),
         }
         // 線形的なデータ構造にした方がいいけど割愛
     }
+
+    pub fn has_connections(&self) -> bool {
+        self.departure_connection.is_some() || self.arrival_connection.is_some()
+    }
+
+    // 在来線の駅は、乗り継ぐ新幹線の駅と結ばれている場合に限り指定できる
+    pub fn has_valid_connections(&self) -> bool {
+        let is_valid = |station: &Station, connection: &Option<ConventionalStation>| {
+            connection.is_none_or(|connection| find_conventional_segment(&connection).connecting_station == *station)
+        };
+        is_valid(&self.departure, &self.departure_connection) && is_valid(&self.arrival, &self.arrival_connection)
+    }
+
+    // 発駅と着駅のうち、特定都区市内の中心駅から運賃を算出する駅の区域を ( 発駅, 着駅 ) の順に返す
+    // 在来線で乗り継ぐ場合は在来線の駅の区域とする
+    // 中心駅に置き換えた営業キロが 200 km を超える場合に限り、両端の区域を適用する
+    pub fn get_city_zones(&self) -> (Option<CityZone>, Option<CityZone>) {
+        let city_zones = (
+            find_endpoint_city_zone(&self.departure, &self.departure_connection),
+            find_endpoint_city_zone(&self.arrival, &self.arrival_connection),
+        );
+        if city_zones == (None, None) || self.calc_city_zone_kilometer() <= CITY_ZONE_MIN_KILOMETER {
+            return (None, None);
+        }
+        city_zones
+    }
+
+    // 運賃の計算に用いる営業キロ
+    // 在来線の区間を含み、特定都区市内を適用する場合は中心駅からの営業キロとする
+    pub fn get_fare_kilometer(&self) -> OperatingKilometer {
+        let value = match self.get_city_zones() {
+            (None, None) => {
+                let connections = [&self.departure_connection, &self.arrival_connection];
+                self.get_operation_kilometer().value
+                    + connections.iter().map(|connection| calc_connection_kilometer(connection)).sum::<f64>()
+            }
+            _ => self.calc_city_zone_kilometer(),
+        };
+        // 浮動小数点の誤差を避けるため 0.1 km 単位に丸める
+        OperatingKilometer { value: (value * 10.0).round() / 10.0 }
+    }
+
+    // 特定都区市内にある端は中心駅まで、それ以外の端は在来線の区間までの営業キロ
+    // 駅の組み合わせは東京寄りの駅が先になるため、東京から遠い側の中心駅からの営業キロを差し引き、近い側を加える
+    fn calc_city_zone_kilometer(&self) -> f64 {
+        let (near, _) = self.get_station_pair();
+        let extension = |station: &Station, connection: &Option<ConventionalStation>| match find_endpoint_city_zone(
+            station, connection,
+        ) {
+            Some(_) => {
+                let kilometer_from_central =
+                    find_city_zone_member(station).map_or(0.0, |member| member.kilometer_from_central);
+                if station == near {
+                    kilometer_from_central
+                } else {
+                    -kilometer_from_central
+                }
+            }
+            None => calc_connection_kilometer(connection),
+        };
+        let value = self.get_operation_kilometer().value
+            + extension(&self.departure, &self.departure_connection)
+            + extension(&self.arrival, &self.arrival_connection);
+        (value * 10.0).round() / 10.0
+    }
+}
+
+fn find_city_zone_member(station: &Station) -> Option<CityZoneMember> {
+    get_city_zone_members().into_iter().find(|member| member.station == *station)
+}
+
+fn find_endpoint_city_zone(station: &Station, connection: &Option<ConventionalStation>) -> Option<CityZone> {
+    match connection {
+        Some(connection) => find_conventional_segment(connection).city_zone,
+        None => find_city_zone_member(station).map(|member| member.city_zone),
+    }
+}
+
+fn calc_connection_kilometer(connection: &Option<ConventionalStation>) -> f64 {
+    connection.map_or(0.0, |connection| find_conventional_segment(&connection).get_fare_kilometer())
+}
+
+// 営業キロが定義されている駅の組み合わせ ( 料金表の網羅性検証に用いる )
+pub fn get_defined_station_pairs() -> Vec<(Station, Station)> {
+    vec![
+        (Station::Tokyo, Station::ShinOsaka),
+        (Station::Tokyo, Station::Himeji),
+        (Station::Tokyo, Station::Hakata),
+        (Station::ShinOsaka, Station::Himeji),
+        (Station::ShinOsaka, Station::Hakata),
+        (Station::Himeji, Station::Hakata),
+        (Station::Tokyo, Station::KagoshimaChuo),
+        (Station::ShinOsaka, Station::KagoshimaChuo),
+        (Station::Himeji, Station::KagoshimaChuo),
+        (Station::Hakata, Station::KagoshimaChuo),
+    ]
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Copy, Debug)]
 pub enum Station {
     Tokyo,
     ShinOsaka,
     Himeji,
+    Hakata,
+    KagoshimaChuo,
+}
+
+impl Station {
+    // 時刻表や運賃表のファイルで駅を表す名前
+    pub fn parse(value: &str) -> anyhow::Result<Station> {
+        match value {
+            "Tokyo" => Ok(Station::Tokyo),
+            "ShinOsaka" => Ok(Station::ShinOsaka),
+            "Himeji" => Ok(Station::Himeji),
+            "Hakata" => Ok(Station::Hakata),
+            "KagoshimaChuo" => Ok(Station::KagoshimaChuo),
+            _ => Err(anyhow!("未知の駅です ( {value} )")),
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Station::Tokyo => "東京",
+            Station::ShinOsaka => "新大阪",
+            Station::Himeji => "姫路",
+            Station::Hakata => "博多",
+            Station::KagoshimaChuo => "鹿児島中央",
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
-    use crate::domain::base::ride_section::Station::{Himeji, ShinOsaka, Tokyo};
+    use crate::domain::base::city_zone::CityZone;
+    use crate::domain::base::city_zone::CityZone::*;
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::conventional_line::ConventionalStation::*;
+    use crate::domain::base::ride_section::Station::{Hakata, Himeji, KagoshimaChuo, ShinOsaka, Tokyo};
     use crate::domain::base::ride_section::{RideSection, Station};
     use crate::fundamental::operating_kilometer::OperatingKilometer;
 
@@ -43,8 +186,80 @@ mod tests {
     #[case(ShinOsaka, Tokyo, 553.0)]
     #[case(Tokyo, Himeji, 644.0)]
     #[case(Himeji, Tokyo, 644.0)]
+    #[case(Hakata, Tokyo, 1174.9)]
+    #[case(ShinOsaka, Himeji, 91.0)]
+    #[case(Hakata, ShinOsaka, 621.9)]
+    #[case(Himeji, Hakata, 530.9)]
+    #[case(KagoshimaChuo, Tokyo, 1463.8)]
+    #[case(Hakata, KagoshimaChuo, 288.9)]
     fn get_operation_kilometer(#[case] departure: Station, #[case] arrival: Station, #[case] exp: f64) {
-        let sut = RideSection { departure, arrival };
+        let sut = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
         assert_eq!(&OperatingKilometer { value: exp }, sut.get_operation_kilometer());
     }
+
+    #[rstest]
+    // 東京都区内 → 大阪市内 ( 大阪から算出する )
+    #[case(Tokyo, ShinOsaka, Some(TokyoWards), Some(OsakaCity), 556.8)]
+    #[case(ShinOsaka, Tokyo, Some(OsakaCity), Some(TokyoWards), 556.8)]
+    // 東京都区内 → 姫路
+    #[case(Tokyo, Himeji, Some(TokyoWards), None, 644.0)]
+    #[case(Himeji, Tokyo, None, Some(TokyoWards), 644.0)]
+    fn city_zones(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] departure_zone: Option<CityZone>,
+        #[case] arrival_zone: Option<CityZone>,
+        #[case] exp: f64,
+    ) {
+        let sut = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!((departure_zone, arrival_zone), sut.get_city_zones());
+        assert_eq!(OperatingKilometer { value: exp }, sut.get_fare_kilometer());
+    }
+
+    #[rstest]
+    // 東京都区内の品川から乗り継ぐ場合は東京から算出する
+    #[case(Some(Shinagawa), Tokyo, ShinOsaka, None, (Some(TokyoWards), Some(OsakaCity)), 556.8)]
+    #[case(None, Himeji, Tokyo, Some(Shinagawa), (None, Some(TokyoWards)), 644.0)]
+    // 幹線は営業キロを加える
+    #[case(None, Tokyo, Himeji, Some(Kakogawa), (Some(TokyoWards), None), 658.2)]
+    // 地方交通線は換算キロを加える
+    #[case(Some(HonTatsuno), Himeji, Tokyo, None, (None, Some(TokyoWards)), 661.2)]
+    fn connections(
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] arrival_connection: Option<ConventionalStation>,
+        #[case] exp_zones: (Option<CityZone>, Option<CityZone>),
+        #[case] exp: f64,
+    ) {
+        let sut = RideSection { departure, arrival, departure_connection, arrival_connection };
+        assert!(sut.has_connections());
+        assert_eq!(exp_zones, sut.get_city_zones());
+        assert_eq!(OperatingKilometer { value: exp }, sut.get_fare_kilometer());
+    }
+
+    #[rstest]
+    #[case(Some(Shinagawa), Tokyo, Himeji, Some(Kakogawa), true)]
+    #[case(None, ShinOsaka, Himeji, Some(HonTatsuno), true)]
+    // 乗り継ぐ新幹線の駅と結ばれていない
+    #[case(Some(Shinagawa), ShinOsaka, Himeji, None, false)]
+    #[case(None, Tokyo, ShinOsaka, Some(Kakogawa), false)]
+    fn valid_connections(
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] arrival_connection: Option<ConventionalStation>,
+        #[case] exp: bool,
+    ) {
+        let sut = RideSection { departure, arrival, departure_connection, arrival_connection };
+        assert_eq!(exp, sut.has_valid_connections());
+    }
+
+    #[rstest]
+    #[case("Tokyo", Some(Tokyo))]
+    #[case("KagoshimaChuo", Some(KagoshimaChuo))]
+    #[case("Osaka", None)]
+    fn parse(#[case] value: &str, #[case] exp: Option<Station>) {
+        assert_eq!(exp, Station::parse(value).ok());
+    }
 }
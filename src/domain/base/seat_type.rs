@@ -1,4 +1,14 @@
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub enum SeatType {
     Reserved,
     Free,
 }
+
+impl SeatType {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            SeatType::Reserved => "指定席",
+            SeatType::Free => "自由席",
+        }
+    }
+}
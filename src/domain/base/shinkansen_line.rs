@@ -0,0 +1,124 @@
+use crate::domain::base::operator::Operator;
+use crate::domain::base::ride_section::{RideSection, Station};
+
+// 新幹線の路線
+// 東海道新幹線と山陽新幹線は新大阪で、山陽新幹線と九州新幹線は博多で接続し、列車は路線をまたいで直通する
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ShinkansenLine {
+    Tokaido,
+    Sanyo,
+    Kyushu,
+}
+
+impl ShinkansenLine {
+    pub fn get_operator(&self) -> Operator {
+        match self {
+            Self::Tokaido => Operator::Central,
+            Self::Sanyo => Operator::West,
+            Self::Kyushu => Operator::Kyushu,
+        }
+    }
+
+    // 路線の駅を東京寄りから順に返す ( 境界駅は両方の路線に含む )
+    pub fn get_stations(&self) -> Vec<Station> {
+        match self {
+            Self::Tokaido => vec![Station::Tokyo, Station::ShinOsaka],
+            Self::Sanyo => vec![Station::ShinOsaka, Station::Himeji, Station::Hakata],
+            Self::Kyushu => vec![Station::Hakata, Station::KagoshimaChuo],
+        }
+    }
+
+    fn contains(&self, station: &Station) -> bool {
+        self.get_stations().contains(station)
+    }
+}
+
+// 東京寄りから順に並べた路線
+const LINES: [ShinkansenLine; 3] = [ShinkansenLine::Tokaido, ShinkansenLine::Sanyo, ShinkansenLine::Kyushu];
+
+// 1 つの路線に収まる乗車区間
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct LineSection {
+    pub line: ShinkansenLine,
+    pub ride_section: RideSection,
+}
+
+// 乗車区間を路線ごとに分け、乗車する順に返す
+// 路線をまたぐ場合は境界駅で分ける ( 在来線の区間は含めない )
+pub fn split_by_line(ride_section: &RideSection) -> Vec<LineSection> {
+    let (near, far) = ride_section.get_station_pair();
+    let near_index = LINES.iter().position(|line| line.contains(near)).unwrap_or_else(|| panic!("unexpected station"));
+    let far_index = LINES.iter().rposition(|line| line.contains(far)).unwrap_or_else(|| panic!("unexpected station"));
+
+    let mut line_sections = vec![];
+    let mut departure = *near;
+    for line in &LINES[near_index..=far_index] {
+        let arrival = if line.contains(far) { *far } else { *line.get_stations().last().unwrap() };
+        if departure != arrival {
+            line_sections.push(LineSection {
+                line: *line,
+                ride_section: RideSection { departure, arrival, departure_connection: None, arrival_connection: None },
+            });
+        }
+        departure = arrival;
+    }
+
+    // 東京へ向かう場合は逆順に乗車する
+    if ride_section.departure == *far {
+        line_sections.reverse();
+        for line_section in &mut line_sections {
+            let section = line_section.ride_section;
+            line_section.ride_section =
+                RideSection { departure: section.arrival, arrival: section.departure, ..section };
+        }
+    }
+    line_sections
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::operator::Operator;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::shinkansen_line::ShinkansenLine::*;
+    use crate::domain::base::shinkansen_line::{split_by_line, LineSection, ShinkansenLine};
+
+    fn ride_section(departure: Station, arrival: Station) -> RideSection {
+        RideSection { departure, arrival, departure_connection: None, arrival_connection: None }
+    }
+
+    #[rstest]
+    #[case(Tokyo, ShinOsaka, vec![(Tokaido, Tokyo, ShinOsaka)])]
+    #[case(ShinOsaka, Hakata, vec![(Sanyo, ShinOsaka, Hakata)])]
+    #[case(Hakata, Himeji, vec![(Sanyo, Hakata, Himeji)])]
+    // 境界駅の新大阪で分ける
+    #[case(Tokyo, Himeji, vec![(Tokaido, Tokyo, ShinOsaka), (Sanyo, ShinOsaka, Himeji)])]
+    #[case(Hakata, Tokyo, vec![(Sanyo, Hakata, ShinOsaka), (Tokaido, ShinOsaka, Tokyo)])]
+    #[case(Hakata, KagoshimaChuo, vec![(Kyushu, Hakata, KagoshimaChuo)])]
+    #[case(
+        KagoshimaChuo,
+        Tokyo,
+        vec![(Kyushu, KagoshimaChuo, Hakata), (Sanyo, Hakata, ShinOsaka), (Tokaido, ShinOsaka, Tokyo)]
+    )]
+    fn split(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] exp: Vec<(ShinkansenLine, Station, Station)>,
+    ) {
+        let exp = exp
+            .into_iter()
+            .map(|(line, departure, arrival)| LineSection { line, ride_section: ride_section(departure, arrival) })
+            .collect::<Vec<_>>();
+        assert_eq!(exp, split_by_line(&ride_section(departure, arrival)));
+    }
+
+    #[rstest]
+    #[case(Tokaido, Operator::Central)]
+    #[case(Sanyo, Operator::West)]
+    #[case(Kyushu, Operator::Kyushu)]
+    fn operator(#[case] line: ShinkansenLine, #[case] exp: Operator) {
+        assert_eq!(exp, line.get_operator());
+    }
+}
@@ -0,0 +1,188 @@
+use crate::domain::base::ride_section::{RideSection, Station};
+use crate::domain::base::train::Train;
+
+// 列車の種類ごとにその駅へ停車する頻度
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum StopFrequency {
+    // すべての列車が停車する
+    Every,
+    // 一部の列車だけが停車する
+    Some,
+    // その駅まで乗り入れない
+    Never,
+}
+
+// 列車の種類と停車駅の組み合わせ
+// 時刻表 ( timetable/timetable.csv ) の停車駅と食い違わないことをテストで確かめる
+#[derive(PartialEq, Debug)]
+pub struct StopPattern {
+    pub train: Train,
+    pub station: Station,
+    pub frequency: StopFrequency,
+}
+
+pub fn get_stop_patterns() -> Vec<StopPattern> {
+    vec![
+        StopPattern { train: Train::Hikari, station: Station::Tokyo, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Hikari, station: Station::ShinOsaka, frequency: StopFrequency::Every },
+        // 岡山方面へ直通するひかりだけが停車する
+        StopPattern { train: Train::Hikari, station: Station::Himeji, frequency: StopFrequency::Some },
+        // 博多まで直通するのは新大阪始発の一部のひかりだけ
+        StopPattern { train: Train::Hikari, station: Station::Hakata, frequency: StopFrequency::Some },
+        // ひかりとのぞみは九州新幹線へ乗り入れない
+        StopPattern { train: Train::Hikari, station: Station::KagoshimaChuo, frequency: StopFrequency::Never },
+        StopPattern { train: Train::Nozomi, station: Station::Tokyo, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Nozomi, station: Station::ShinOsaka, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Nozomi, station: Station::Himeji, frequency: StopFrequency::Some },
+        // 新大阪や広島で折り返すのぞみもある
+        StopPattern { train: Train::Nozomi, station: Station::Hakata, frequency: StopFrequency::Some },
+        StopPattern { train: Train::Nozomi, station: Station::KagoshimaChuo, frequency: StopFrequency::Never },
+        // さくらとみずほは新大阪から九州新幹線へ直通する ( 東京には乗り入れない )
+        StopPattern { train: Train::Sakura, station: Station::Tokyo, frequency: StopFrequency::Never },
+        StopPattern { train: Train::Sakura, station: Station::ShinOsaka, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Sakura, station: Station::Himeji, frequency: StopFrequency::Some },
+        StopPattern { train: Train::Sakura, station: Station::Hakata, frequency: StopFrequency::Every },
+        // 熊本で折り返すさくらもある
+        StopPattern { train: Train::Sakura, station: Station::KagoshimaChuo, frequency: StopFrequency::Some },
+        StopPattern { train: Train::Mizuho, station: Station::Tokyo, frequency: StopFrequency::Never },
+        StopPattern { train: Train::Mizuho, station: Station::ShinOsaka, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Mizuho, station: Station::Himeji, frequency: StopFrequency::Some },
+        StopPattern { train: Train::Mizuho, station: Station::Hakata, frequency: StopFrequency::Every },
+        StopPattern { train: Train::Mizuho, station: Station::KagoshimaChuo, frequency: StopFrequency::Every },
+    ]
+}
+
+#[derive(PartialEq, Debug)]
+pub enum StopPatternViolation {
+    NotServed { train: Train, station: Station },
+}
+
+impl StopPatternViolation {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NotServed { train, station } => {
+                format!("{}は{}に停車しません", train.describe(), station.describe())
+            }
+        }
+    }
+}
+
+// 一部の列車だけが停車する駅について、停車する列車を選ぶよう促す
+pub fn describe_partly_served(train: &Train, station: &Station) -> String {
+    format!("{}に停車しない{}もあります", station.describe(), train.describe())
+}
+
+// 発駅と着駅のどちらかに停車しない列車は受け付けない ( 表に載っていない駅にも停車しないものとする )
+// 一部の列車だけが停車する駅を返し、呼び出し側で警告する
+pub fn judge_stop_pattern(
+    train: &Train,
+    ride_section: &RideSection,
+    stop_patterns: &[StopPattern],
+) -> Result<Vec<Station>, StopPatternViolation> {
+    let mut partly_served = vec![];
+    for station in [ride_section.departure, ride_section.arrival] {
+        let stop_pattern = stop_patterns
+            .iter()
+            .find(|stop_pattern| stop_pattern.train == *train && stop_pattern.station == station)
+            .filter(|stop_pattern| stop_pattern.frequency != StopFrequency::Never)
+            .ok_or(StopPatternViolation::NotServed { train: *train, station })?;
+        if stop_pattern.frequency == StopFrequency::Some {
+            partly_served.push(station);
+        }
+    }
+    Ok(partly_served)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::stop_pattern::{
+        describe_partly_served, get_stop_patterns, judge_stop_pattern, StopFrequency, StopPattern, StopPatternViolation,
+    };
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::schedule::timetable::parse_timetable;
+
+    #[rstest]
+    #[case(Nozomi, Tokyo, ShinOsaka, vec![])]
+    #[case(Hikari, ShinOsaka, Tokyo, vec![])]
+    #[case(Nozomi, Tokyo, Himeji, vec![Himeji])]
+    #[case(Hikari, Himeji, Tokyo, vec![Himeji])]
+    #[case(Nozomi, Himeji, Hakata, vec![Himeji, Hakata])]
+    #[case(Mizuho, ShinOsaka, KagoshimaChuo, vec![])]
+    #[case(Sakura, KagoshimaChuo, Himeji, vec![KagoshimaChuo, Himeji])]
+    fn served(#[case] train: Train, #[case] departure: Station, #[case] arrival: Station, #[case] exp: Vec<Station>) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(Ok(exp), judge_stop_pattern(&train, &ride_section, &get_stop_patterns()));
+    }
+
+    #[rstest]
+    #[case(Nozomi, Tokyo, KagoshimaChuo, KagoshimaChuo)]
+    #[case(Hikari, KagoshimaChuo, ShinOsaka, KagoshimaChuo)]
+    #[case(Sakura, Tokyo, Hakata, Tokyo)]
+    #[case(Mizuho, KagoshimaChuo, Tokyo, Tokyo)]
+    fn not_served_by_stop_patterns(
+        #[case] train: Train,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] station: Station,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            Err(StopPatternViolation::NotServed { train, station }),
+            judge_stop_pattern(&train, &ride_section, &get_stop_patterns())
+        );
+    }
+
+    // 時刻表の列車は始発から終着までの間ですべての列車が停車する駅に停車し、乗り入れない駅には停車しない
+    #[test]
+    fn consistent_with_timetable() {
+        let timetable = parse_timetable(include_str!("../../../timetable/timetable.csv")).unwrap();
+        let stop_patterns = get_stop_patterns();
+        for entry in &timetable.entries {
+            let first = entry.stops.first().unwrap().station;
+            let last = entry.stops.last().unwrap().station;
+            let (from, to) = (first.min(last), first.max(last));
+            for stop_pattern in stop_patterns.iter().filter(|stop_pattern| stop_pattern.train == entry.train) {
+                let stops = entry.stops.iter().any(|stop| stop.station == stop_pattern.station);
+                let passes = from <= stop_pattern.station && stop_pattern.station <= to;
+                match stop_pattern.frequency {
+                    StopFrequency::Every => assert!(!passes || stops, "{:?} {}", entry.train, entry.number),
+                    StopFrequency::Some => {}
+                    StopFrequency::Never => assert!(!stops, "{:?} {}", entry.train, entry.number),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn not_served() {
+        // 姫路に停車しないのぞみだけを運転する場合
+        let stop_patterns = [
+            StopPattern { train: Nozomi, station: Tokyo, frequency: StopFrequency::Every },
+            StopPattern { train: Nozomi, station: ShinOsaka, frequency: StopFrequency::Every },
+        ];
+        let ride_section =
+            RideSection { departure: Tokyo, arrival: Himeji, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            Err(StopPatternViolation::NotServed { train: Nozomi, station: Himeji }),
+            judge_stop_pattern(&Nozomi, &ride_section, &stop_patterns)
+        );
+        assert_eq!(
+            Err(StopPatternViolation::NotServed { train: Hikari, station: Tokyo }),
+            judge_stop_pattern(&Hikari, &ride_section, &stop_patterns)
+        );
+    }
+
+    #[test]
+    fn describe() {
+        assert_eq!(
+            "のぞみは姫路に停車しません",
+            StopPatternViolation::NotServed { train: Nozomi, station: Himeji }.describe()
+        );
+        assert_eq!("姫路に停車しないのぞみもあります", describe_partly_served(&Nozomi, &Himeji));
+    }
+}
@@ -1,4 +1,63 @@
+use anyhow::anyhow;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
 pub enum Train {
     Hikari,
     Nozomi,
+    // 山陽新幹線と九州新幹線を直通する
+    Sakura,
+    Mizuho,
+}
+
+impl Train {
+    // 時刻表や在庫のファイルで列車を表す名前
+    pub fn parse(value: &str) -> anyhow::Result<Train> {
+        match value {
+            "Hikari" => Ok(Train::Hikari),
+            "Nozomi" => Ok(Train::Nozomi),
+            "Sakura" => Ok(Train::Sakura),
+            "Mizuho" => Ok(Train::Mizuho),
+            _ => Err(anyhow!("未知の列車です ( {value} )")),
+        }
+    }
+
+    pub fn format(&self) -> &'static str {
+        match self {
+            Train::Hikari => "Hikari",
+            Train::Nozomi => "Nozomi",
+            Train::Sakura => "Sakura",
+            Train::Mizuho => "Mizuho",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Train::Hikari => "ひかり",
+            Train::Nozomi => "のぞみ",
+            Train::Sakura => "さくら",
+            Train::Mizuho => "みずほ",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+
+    #[rstest]
+    #[case(Hikari)]
+    #[case(Nozomi)]
+    #[case(Sakura)]
+    #[case(Mizuho)]
+    fn parse_format(#[case] train: Train) {
+        assert_eq!(train, Train::parse(train.format()).unwrap());
+    }
+
+    #[test]
+    fn parse_unknown() {
+        assert_eq!("未知の列車です ( Kodama )", Train::parse("Kodama").unwrap_err().to_string());
+    }
 }
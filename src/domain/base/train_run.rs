@@ -0,0 +1,10 @@
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::train::Train;
+
+// 乗車日に運転される個々の列車 ( のぞみ 1 号など )
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct TrainRun {
+    pub train: Train,
+    pub number: u32,
+    pub departure_date: DepartureDate,
+}
@@ -0,0 +1 @@
+pub mod ticket_change;
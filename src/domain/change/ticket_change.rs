@@ -0,0 +1,214 @@
+use crate::domain::base::cancellation_date::CancellationDate;
+use crate::domain::base::change_date::ChangeDate;
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::fare::ex_express_fare::calc_ex_express_fare;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::single_trip_adult_fare::calc_single_trip_adult_fare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::domain::refund::cancellation_refund::{calc_refund, IssuedFare};
+use crate::fundamental::amount::Amount;
+
+// 変更前後の予約内容
+pub struct Reservation {
+    pub ride_section: RideSection,
+    pub train: Train,
+    pub seat_type: SeatType,
+    pub departure_date: DepartureDate,
+    // EX サービスで予約した場合の商品
+    pub ex_product: Option<ExProduct>,
+}
+
+impl Reservation {
+    // EX サービスで予約した場合は商品ごとの特急料金を用いる
+    fn calc_fare(&self) -> anyhow::Result<(TrainFare, ExpressFare)> {
+        let fare = calc_single_trip_adult_fare(&self.ride_section, &self.train, &self.seat_type, &self.departure_date)?;
+        let express_fare = match &self.ex_product {
+            Some(ex_product) => calc_ex_express_fare(
+                ex_product,
+                &self.train,
+                &self.seat_type,
+                &self.ride_section,
+                &self.departure_date,
+            )?,
+            None => fare.express_fare,
+        };
+        Ok((fare.train_fare, express_fare))
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Settlement {
+    Payment(Amount),
+    Refund(Amount),
+    Even,
+}
+
+// settlement は変更前後の料金の差額で、手数料を含めない
+// 旅客が支払う額は追加支払と手数料の合計、受け取る額は差額返金から手数料を差し引いた額になる
+#[derive(PartialEq, Debug)]
+pub struct TicketChange {
+    pub handling_fee: Amount,
+    pub settlement: Settlement,
+}
+
+// 1 回目の変更は手数料なしで差額を精算する
+// 2 回目以降の変更は払戻と買い直しとして扱うため、変更日を払戻日とした払戻手数料を別に収受する
+// EX サービスの予約は出発前であれば何回でも手数料なしで変更できる
+// 変更前の出発日を過ぎている場合は変更できないので None を返す
+pub fn calc_ticket_change(
+    current: &Reservation,
+    requested: &Reservation,
+    previous_change_count: usize,
+    change_date: &ChangeDate,
+) -> anyhow::Result<Option<TicketChange>> {
+    let (current_train_fare, current_express_fare) = current.calc_fare()?;
+    let (requested_train_fare, requested_express_fare) = requested.calc_fare()?;
+
+    if change_date.get_days_before(&current.departure_date) < 0 {
+        return Ok(None);
+    }
+    let credited = current_train_fare.value.clone() + current_express_fare.value.clone();
+
+    let handling_fee = if previous_change_count == 0 || current.ex_product.is_some() {
+        Amount { value: 0 }
+    } else {
+        let issued_fare = IssuedFare {
+            train_fare: current_train_fare,
+            express_fare: current_express_fare,
+            seat_type: current.seat_type,
+            ex_product: current.ex_product,
+        };
+        let cancellation_date = CancellationDate { value: change_date.value };
+        let Some(refund) = calc_refund(&issued_fare, &current.departure_date, &cancellation_date) else {
+            return Ok(None);
+        };
+        refund.handling_fee
+    };
+
+    let charged = requested_train_fare.value + requested_express_fare.value;
+    let settlement = if credited.value < charged.value {
+        Settlement::Payment(charged - credited)
+    } else if charged.value < credited.value {
+        Settlement::Refund(credited - charged)
+    } else {
+        Settlement::Even
+    };
+
+    Ok(Some(TicketChange { handling_fee, settlement }))
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::change_date::ChangeDate;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ex_product::ExProduct;
+    use crate::domain::base::ex_product::ExProduct::*;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::change::ticket_change::Settlement::*;
+    use crate::domain::change::ticket_change::{calc_ticket_change, Reservation, Settlement, TicketChange};
+    use crate::fundamental::amount::Amount;
+
+    fn reservation(train: Train, seat_type: SeatType, m: u32, d: u32) -> Reservation {
+        Reservation {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train,
+            seat_type,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() },
+            ex_product: None,
+        }
+    }
+
+    #[rstest]
+    // 1 回目 - 同額
+    #[case(Nozomi, Reserved, 2, 1, Nozomi, Reserved, 2, 2, 0, 0, Even)]
+    // 1 回目 - 列車の変更 ( 追加支払い )
+    #[case(Hikari, Reserved, 2, 1, Nozomi, Reserved, 2, 1, 0, 0, Payment(Amount { value: 530 }))]
+    // 1 回目 - 座席の変更 ( 差額の返金 )
+    #[case(Nozomi, Reserved, 2, 1, Nozomi, Free, 2, 1, 0, 0, Refund(Amount { value: 1060 }))]
+    // 1 回目 - 閑散期から繁忙期への変更
+    #[case(Nozomi, Reserved, 1, 20, Nozomi, Reserved, 1, 10, 0, 0, Payment(Amount { value: 400 }))]
+    // 1 回目 - 繁忙期から閑散期への変更
+    #[case(Nozomi, Reserved, 1, 10, Nozomi, Reserved, 1, 20, 0, 0, Refund(Amount { value: 400 }))]
+    // 2 回目 - 払戻手数料を差額とは別に収受する
+    #[case(Nozomi, Reserved, 2, 1, Nozomi, Reserved, 2, 2, 1, 560, Even)]
+    #[case(Nozomi, Reserved, 2, 1, Nozomi, Free, 2, 1, 1, 560, Refund(Amount { value: 1060 }))]
+    #[case(Hikari, Reserved, 2, 1, Nozomi, Reserved, 2, 1, 2, 560, Payment(Amount { value: 530 }))]
+    fn test_calc_ticket_change(
+        #[case] current_train: Train,
+        #[case] current_seat_type: SeatType,
+        #[case] current_m: u32,
+        #[case] current_d: u32,
+        #[case] requested_train: Train,
+        #[case] requested_seat_type: SeatType,
+        #[case] requested_m: u32,
+        #[case] requested_d: u32,
+        #[case] previous_change_count: usize,
+        #[case] handling_fee: u64,
+        #[case] settlement: Settlement,
+    ) {
+        let current = reservation(current_train, current_seat_type, current_m, current_d);
+        let requested = reservation(requested_train, requested_seat_type, requested_m, requested_d);
+        let change_date = ChangeDate { value: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() };
+        assert_eq!(
+            Some(TicketChange { handling_fee: Amount { value: handling_fee }, settlement }),
+            calc_ticket_change(&current, &requested, previous_change_count, &change_date).unwrap()
+        );
+    }
+
+    #[rstest]
+    // スマートEX は 2 回目以降の変更でも手数料なしで差額を精算する
+    #[case(SmartEx, Hikari, Nozomi, 2, Payment(Amount { value: 530 }))]
+    // EX予約 は会員価格どうしの差額を精算する
+    #[case(ExReservation, Nozomi, Hikari, 0, Refund(Amount { value: 510 }))]
+    #[case(ExReservation, Nozomi, Nozomi, 3, Even)]
+    fn ex_product_change(
+        #[case] ex_product: ExProduct,
+        #[case] current_train: Train,
+        #[case] requested_train: Train,
+        #[case] previous_change_count: usize,
+        #[case] settlement: Settlement,
+    ) {
+        let current = Reservation { ex_product: Some(ex_product), ..reservation(current_train, Reserved, 2, 1) };
+        let requested = Reservation { ex_product: Some(ex_product), ..reservation(requested_train, Reserved, 2, 2) };
+        let change_date = ChangeDate { value: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() };
+        assert_eq!(
+            Some(TicketChange { handling_fee: Amount { value: 0 }, settlement }),
+            calc_ticket_change(&current, &requested, previous_change_count, &change_date).unwrap()
+        );
+    }
+
+    #[test]
+    fn ex_product_not_covered() {
+        let current = Reservation { ex_product: Some(ExReservation), ..reservation(Sakura, Reserved, 2, 1) };
+        let requested = reservation(Nozomi, Reserved, 2, 1);
+        let change_date = ChangeDate { value: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() };
+        let act = calc_ticket_change(&current, &requested, 0, &change_date);
+        assert_eq!("EX予約 の対象外区間です", act.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_calc_ticket_change_after_departure() {
+        let current = reservation(Nozomi, Reserved, 2, 1);
+        let requested = reservation(Nozomi, Reserved, 2, 2);
+        let change_date = ChangeDate { value: NaiveDate::from_ymd_opt(2024, 2, 2).unwrap() };
+        assert_eq!(None, calc_ticket_change(&current, &requested, 0, &change_date).unwrap());
+    }
+}
@@ -0,0 +1,544 @@
+use itertools::Itertools;
+
+use crate::domain::discount::individual_discount::IndividualDiscount;
+use crate::domain::discount::overall_discount::GroupFreePassengerPolicy;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::fundamental::amount::Amount;
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// 割引を適用する対象
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum DiscountTarget {
+    TrainFare,
+    ExpressFare,
+    // 運賃 + 特急料金
+    SingleFare,
+}
+
+// 割引の方法
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum DiscountMethod {
+    // 割り引いた後の割合 ( 0.9 なら 1 割引 )
+    Rate(Fraction),
+    FixedAmount(Amount),
+}
+
+// ほかの割引との重ね方 ( 割引ごとに宣言する )
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum StackingMode {
+    // 優先度順に 1 つずつ適用する ( 0.9 -> 0.85 )
+    Sequential,
+    // 同じく掛け合わせる割引と割引率を掛け合わせてから 1 度だけ適用する ( 0.765 )
+    // 金額による割引は掛け合わせた割引率の適用後に優先度順に差し引く
+    Multiplicative,
+}
+
+// 割引を適用した後に端数を処理するタイミング ( 割引ごとに宣言する )
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum RoundingPoint {
+    // この割引を適用した直後に処理する
+    EachStep,
+    // すべての割引を適用した後にまとめて処理する
+    Last,
+}
+
+// 運賃 + 特急料金への割引の切り捨て方
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum SingleFareRounding {
+    // train * rate + express * rate
+    PerComponent,
+    // (train + express) * rate
+    Combined,
+}
+
+// 割引が宣言した重ね方と端数処理のタイミングを、割引の名前を指定して置き換える設定
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct StackingOverride {
+    pub name: String,
+    pub stacking_mode: StackingMode,
+    pub rounding_point: RoundingPoint,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct DiscountPolicy {
+    pub stacking_overrides: Vec<StackingOverride>,
+    pub single_fare_rounding: SingleFareRounding,
+    pub group_free_passenger: GroupFreePassengerPolicy,
+}
+
+impl Default for DiscountPolicy {
+    // 割引の宣言に従い、運賃と特急料金を個別に切り捨てる
+    fn default() -> Self {
+        Self {
+            stacking_overrides: vec![],
+            single_fare_rounding: SingleFareRounding::PerComponent,
+            group_free_passenger: GroupFreePassengerPolicy::default(),
+        }
+    }
+}
+
+impl DiscountPolicy {
+    // 設定で置き換えていなければ割引の宣言に従う
+    fn get_stacking(&self, discount: &IndividualDiscount) -> (StackingMode, RoundingPoint) {
+        self.stacking_overrides
+            .iter()
+            .find(|stacking_override| stacking_override.name == discount.name)
+            .map_or((discount.stacking_mode, discount.rounding_point), |stacking_override| {
+                (stacking_override.stacking_mode, stacking_override.rounding_point)
+            })
+    }
+}
+
+// 優先度順に並べた割引を適用する単位
+// 掛け合わせる割引は、そのうち最も優先度の高い割引の位置でまとめて適用する
+enum DiscountStep<'a> {
+    Single(&'a IndividualDiscount, RoundingPoint),
+    Combined(Vec<&'a IndividualDiscount>, RoundingPoint),
+}
+
+pub fn apply_discounts(
+    fare: (TrainFare, ExpressFare),
+    individual_discounts: &[IndividualDiscount],
+    discount_policy: &DiscountPolicy,
+) -> (TrainFare, ExpressFare) {
+    let mut steps: Vec<DiscountStep> = vec![];
+    for discount in individual_discounts.iter().sorted_by_key(|discount| discount.priority) {
+        let (stacking_mode, rounding_point) = discount_policy.get_stacking(discount);
+        match stacking_mode {
+            StackingMode::Sequential => steps.push(DiscountStep::Single(discount, rounding_point)),
+            StackingMode::Multiplicative => {
+                match steps.iter_mut().find_map(|step| match step {
+                    DiscountStep::Combined(discounts, _) => Some(discounts),
+                    DiscountStep::Single(..) => None,
+                }) {
+                    Some(discounts) => discounts.push(discount),
+                    None => steps.push(DiscountStep::Combined(vec![discount], rounding_point)),
+                }
+            }
+        }
+    }
+
+    let mut fare = UnroundedFare::new(fare);
+    for step in steps {
+        let rounding_point = match step {
+            DiscountStep::Single(discount, rounding_point) => {
+                discount.rule.apply(discount, &mut fare);
+                rounding_point
+            }
+            DiscountStep::Combined(discounts, rounding_point) => {
+                for (target, discount_method, rounding) in combine_discount_rates(&discounts) {
+                    fare.apply(target, &discount_method, rounding);
+                }
+                rounding_point
+            }
+        };
+        if rounding_point == RoundingPoint::EachStep {
+            fare.round(discount_policy.single_fare_rounding);
+        }
+    }
+    fare.round(discount_policy.single_fare_rounding);
+
+    fare.into_fare()
+}
+
+// 対象ごとに割引率を掛け合わせる
+// すべての割引率が運賃 + 特急料金を対象にする場合に限り、運賃 + 特急料金への 1 つの割引にまとめる
+// まとめた割引の端数処理は、その対象の割引のうち最も優先度の高い割引に従う
+fn combine_discount_rates(discounts: &[&IndividualDiscount]) -> Vec<(DiscountTarget, DiscountMethod, Rounding)> {
+    let (rates, fixed_amounts): (Vec<&IndividualDiscount>, Vec<&IndividualDiscount>) =
+        discounts.iter().partition(|discount| matches!(discount.discount_method, DiscountMethod::Rate(_)));
+
+    let product = |target: DiscountTarget, targets: &[DiscountTarget]| {
+        let rates = rates.iter().filter(|discount| targets.contains(&discount.target)).collect_vec();
+        let rounding = rates.first()?.rounding;
+        let rate = rates.iter().fold(Fraction::ONE, |product, discount| match discount.discount_method {
+            DiscountMethod::Rate(rate) => product * rate,
+            DiscountMethod::FixedAmount(_) => product,
+        });
+        Some((target, DiscountMethod::Rate(rate), rounding))
+    };
+
+    let mut steps = if rates.iter().all(|discount| discount.target == DiscountTarget::SingleFare) {
+        product(DiscountTarget::SingleFare, &[DiscountTarget::SingleFare]).into_iter().collect_vec()
+    } else {
+        [
+            product(DiscountTarget::TrainFare, &[DiscountTarget::TrainFare, DiscountTarget::SingleFare]),
+            product(DiscountTarget::ExpressFare, &[DiscountTarget::ExpressFare, DiscountTarget::SingleFare]),
+        ]
+        .into_iter()
+        .flatten()
+        .collect_vec()
+    };
+    steps.extend(
+        fixed_amounts.iter().map(|discount| (discount.target, discount.discount_method.clone(), discount.rounding)),
+    );
+    steps
+}
+
+// 端数処理前の金額と、割引を適用した ( 端数処理が必要な ) 対象ごとの端数処理を保持する
+// 端数処理の前に同じ対象へ複数の割引を適用した場合は、最後に適用した割引の端数処理に従う
+// 端数処理前の金額は分数で持ち、誤差なく計算する
+pub struct UnroundedFare {
+    train: Fraction,
+    express: Fraction,
+    train_rounding: Option<Rounding>,
+    express_rounding: Option<Rounding>,
+    single_fare_rounding: Option<Rounding>,
+}
+
+impl UnroundedFare {
+    fn new(fare: (TrainFare, ExpressFare)) -> Self {
+        Self {
+            train: Fraction::integer(fare.0.value.value),
+            express: Fraction::integer(fare.1.value.value),
+            train_rounding: None,
+            express_rounding: None,
+            single_fare_rounding: None,
+        }
+    }
+
+    pub fn apply(&mut self, target: DiscountTarget, discount_method: &DiscountMethod, rounding: Rounding) {
+        match discount_method {
+            DiscountMethod::Rate(discount_rate) => self.apply_rate(target, *discount_rate, rounding),
+            DiscountMethod::FixedAmount(amount) => self.subtract(target, Fraction::integer(amount.value)),
+        }
+    }
+
+    pub fn apply_rate(&mut self, target: DiscountTarget, discount_rate: Fraction, rounding: Rounding) {
+        match target {
+            DiscountTarget::TrainFare => {
+                self.train = self.train * discount_rate;
+                self.train_rounding = Some(rounding);
+            }
+            DiscountTarget::ExpressFare => {
+                self.express = self.express * discount_rate;
+                self.express_rounding = Some(rounding);
+            }
+            DiscountTarget::SingleFare => {
+                self.train = self.train * discount_rate;
+                self.express = self.express * discount_rate;
+                self.train_rounding = Some(rounding);
+                self.express_rounding = Some(rounding);
+                self.single_fare_rounding = Some(rounding);
+            }
+        }
+    }
+
+    // 0 円を下回らないように差し引く
+    // 運賃 + 特急料金が対象の場合は特急料金から差し引き、引ききれない分を運賃から差し引く
+    // 端数処理前の特急料金から引ききれない分は端数を含むため、運賃は特急料金の端数処理を引き継ぐ
+    pub fn subtract(&mut self, target: DiscountTarget, amount: Fraction) {
+        match target {
+            DiscountTarget::TrainFare => self.train = self.train.saturating_sub(amount),
+            DiscountTarget::ExpressFare => self.express = self.express.saturating_sub(amount),
+            DiscountTarget::SingleFare => {
+                let rest = amount.saturating_sub(self.express);
+                self.express = self.express.saturating_sub(amount);
+                self.train = self.train.saturating_sub(rest);
+                if self.train.get_denominator() != 1 && self.train_rounding.is_none() {
+                    self.train_rounding = self.express_rounding;
+                }
+            }
+        }
+    }
+
+    fn round(&mut self, single_fare_rounding: SingleFareRounding) {
+        match (self.single_fare_rounding, single_fare_rounding) {
+            (Some(rounding), SingleFareRounding::Combined) => {
+                let total = rounding.round(&(self.train + self.express));
+                let train = rounding.round(&self.train);
+                self.train = Fraction::integer(train);
+                self.express = Fraction::integer(total.saturating_sub(train));
+            }
+            _ => {
+                if let Some(rounding) = self.train_rounding {
+                    self.train = Fraction::integer(rounding.round(&self.train));
+                }
+                if let Some(rounding) = self.express_rounding {
+                    self.express = Fraction::integer(rounding.round(&self.express));
+                }
+            }
+        }
+        self.train_rounding = None;
+        self.express_rounding = None;
+        self.single_fare_rounding = None;
+    }
+
+    // 端数を含む金額はすべて端数処理の対象になるため、端数処理の後は整数
+    fn into_fare(self) -> (TrainFare, ExpressFare) {
+        assert_eq!(1, self.train.get_denominator(), "train fare must be rounded");
+        assert_eq!(1, self.express.get_denominator(), "express fare must be rounded");
+        (
+            TrainFare { value: Amount { value: self.train.get_numerator() } },
+            ExpressFare { value: Amount { value: self.express.get_numerator() } },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use rstest::rstest;
+
+    use crate::domain::discount::discount_policy::DiscountMethod::*;
+    use crate::domain::discount::discount_policy::DiscountTarget::*;
+    use crate::domain::discount::discount_policy::RoundingPoint::*;
+    use crate::domain::discount::discount_policy::SingleFareRounding::*;
+    use crate::domain::discount::discount_policy::StackingMode::*;
+    use crate::domain::discount::discount_policy::{
+        apply_discounts, DiscountMethod, DiscountPolicy, DiscountTarget, RoundingPoint, SingleFareRounding,
+        StackingMode, StackingOverride,
+    };
+    use crate::domain::discount::group_discount::create_group_discount_under_30;
+    use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+    use crate::domain::discount::round_trip_discount::{create_round_trip_discount, RoundTripDiscountRule};
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::rounding::RoundingMode::*;
+    use crate::fundamental::rounding::RoundingUnit::*;
+    use crate::fundamental::rounding::{Rounding, RoundingMode, RoundingUnit};
+
+    fn discount(target: DiscountTarget, discount_method: DiscountMethod, priority: u8) -> IndividualDiscount {
+        IndividualDiscount {
+            rule: Rc::new(RoundTripDiscountRule),
+            name: "割引".to_string(),
+            target,
+            passenger_target: PassengerTarget::All,
+            priority,
+            discount_method,
+            rounding: Rounding::FLOOR_TO_TEN,
+            stacking_mode: Sequential,
+            rounding_point: EachStep,
+        }
+    }
+
+    // すべての割引の重ね方と端数処理のタイミングを揃える
+    fn stack(
+        individual_discounts: Vec<IndividualDiscount>,
+        stacking_mode: StackingMode,
+        rounding_point: RoundingPoint,
+    ) -> Vec<IndividualDiscount> {
+        individual_discounts
+            .into_iter()
+            .map(|discount| IndividualDiscount { stacking_mode, rounding_point, ..discount })
+            .collect()
+    }
+
+    fn policy(single_fare_rounding: SingleFareRounding) -> DiscountPolicy {
+        DiscountPolicy { single_fare_rounding, ..DiscountPolicy::default() }
+    }
+
+    fn fare(train_fare: u64, express_fare: u64) -> (TrainFare, ExpressFare) {
+        (TrainFare { value: Amount { value: train_fare } }, ExpressFare { value: Amount { value: express_fare } })
+    }
+
+    #[rstest]
+    // 割引なし
+    #[case(Sequential, EachStep, PerComponent, vec![], 8910, 5490)]
+    #[case(Multiplicative, Last, Combined, vec![], 8910, 5490)]
+    // 往復割引 ( 運賃のみ ) & 団体割引 ( 運賃 + 特急料金 )
+    #[case(Sequential, EachStep, PerComponent, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7200, 4940)]
+    #[case(Sequential, EachStep, Combined, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7200, 4950)]
+    #[case(Sequential, Last, PerComponent, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    #[case(Sequential, Last, Combined, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    #[case(Multiplicative, EachStep, PerComponent, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    #[case(Multiplicative, EachStep, Combined, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    #[case(Multiplicative, Last, PerComponent, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    #[case(Multiplicative, Last, Combined, vec![create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))], 7210, 4940)]
+    // 団体割引 ( 運賃 + 特急料金 ) のみ
+    #[case(Sequential, EachStep, PerComponent, vec![create_group_discount_under_30(Fraction::percent(90))], 8010, 4940)]
+    #[case(Sequential, EachStep, Combined, vec![create_group_discount_under_30(Fraction::percent(90))], 8010, 4950)]
+    #[case(Multiplicative, Last, PerComponent, vec![create_group_discount_under_30(Fraction::percent(90))], 8010, 4940)]
+    #[case(Multiplicative, Last, Combined, vec![create_group_discount_under_30(Fraction::percent(90))], 8010, 4950)]
+    // 団体割引の 2 重適用 ( 0.9 -> 0.85 と 0.765 )
+    #[case(Sequential, EachStep, PerComponent, vec![create_group_discount_under_30(Fraction::percent(90)), create_group_discount_under_30(Fraction::percent(85))], 6800, 4190)]
+    #[case(Sequential, EachStep, PerComponent, vec![create_group_discount_under_30(Fraction::percent(85)), create_group_discount_under_30(Fraction::percent(90))], 6810, 4190)]
+    #[case(Multiplicative, EachStep, PerComponent, vec![create_group_discount_under_30(Fraction::percent(90)), create_group_discount_under_30(Fraction::percent(85))], 6810, 4190)]
+    #[case(Multiplicative, EachStep, PerComponent, vec![create_group_discount_under_30(Fraction::percent(85)), create_group_discount_under_30(Fraction::percent(90))], 6810, 4190)]
+    fn test_apply_discounts(
+        #[case] stacking_mode: StackingMode,
+        #[case] rounding_point: RoundingPoint,
+        #[case] single_fare_rounding: SingleFareRounding,
+        #[case] individual_discounts: Vec<IndividualDiscount>,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        assert_eq!(
+            fare(train_fare, express_fare),
+            apply_discounts(
+                fare(8910, 5490),
+                &stack(individual_discounts, stacking_mode, rounding_point),
+                &policy(single_fare_rounding)
+            )
+        );
+    }
+
+    #[rstest]
+    // 割引ごとに指定した端数処理に従う
+    #[case(Floor, Ten, 8010, 4940)]
+    #[case(Floor, One, 8019, 4941)]
+    #[case(Ceil, Ten, 8020, 4950)]
+    fn rounding_of_discount(
+        #[case] mode: RoundingMode,
+        #[case] unit: RoundingUnit,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        let individual_discount = IndividualDiscount {
+            rounding: Rounding { mode, unit },
+            ..discount(SingleFare, Rate(Fraction::percent(90)), 2)
+        };
+        assert_eq!(
+            fare(train_fare, express_fare),
+            apply_discounts(fare(8910, 5490), &[individual_discount], &DiscountPolicy::default())
+        );
+    }
+
+    #[rstest]
+    #[case(Sequential, EachStep, PerComponent)]
+    #[case(Sequential, EachStep, Combined)]
+    #[case(Sequential, Last, PerComponent)]
+    #[case(Sequential, Last, Combined)]
+    #[case(Multiplicative, EachStep, PerComponent)]
+    #[case(Multiplicative, EachStep, Combined)]
+    #[case(Multiplicative, Last, PerComponent)]
+    #[case(Multiplicative, Last, Combined)]
+    fn priority_decides_order(
+        #[case] stacking_mode: StackingMode,
+        #[case] rounding_point: RoundingPoint,
+        #[case] single_fare_rounding: SingleFareRounding,
+    ) {
+        let discounts = [create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(85))];
+        let reversed = discounts.iter().rev().cloned().collect();
+        assert_eq!(
+            apply_discounts(
+                fare(10010, 5920),
+                &stack(discounts.to_vec(), stacking_mode, rounding_point),
+                &policy(single_fare_rounding)
+            ),
+            apply_discounts(
+                fare(10010, 5920),
+                &stack(reversed, stacking_mode, rounding_point),
+                &policy(single_fare_rounding)
+            ),
+        );
+    }
+
+    #[rstest]
+    // 金額による割引
+    #[case(Sequential, vec![discount(ExpressFare, FixedAmount(Amount { value: 1000 }), 3)], 8910, 4490)]
+    #[case(Sequential, vec![discount(SingleFare, FixedAmount(Amount { value: 6000 }), 3)], 8400, 0)]
+    #[case(Sequential, vec![discount(TrainFare, FixedAmount(Amount { value: 10000 }), 3)], 0, 5490)]
+    // 金額による割引と割引率の重ね合わせ
+    #[case(Sequential, vec![discount(ExpressFare, FixedAmount(Amount { value: 1000 }), 1), create_group_discount_under_30(Fraction::percent(90))], 8010, 4040)]
+    #[case(Sequential, vec![discount(ExpressFare, FixedAmount(Amount { value: 1000 }), 3), create_group_discount_under_30(Fraction::percent(90))], 8010, 3940)]
+    #[case(Multiplicative, vec![discount(ExpressFare, FixedAmount(Amount { value: 1000 }), 1), create_group_discount_under_30(Fraction::percent(90))], 8010, 3940)]
+    fn test_apply_fixed_amount(
+        #[case] stacking_mode: StackingMode,
+        #[case] individual_discounts: Vec<IndividualDiscount>,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        assert_eq!(
+            fare(train_fare, express_fare),
+            apply_discounts(
+                fare(8910, 5490),
+                &stack(individual_discounts, stacking_mode, EachStep),
+                &DiscountPolicy::default()
+            )
+        );
+    }
+
+    // 端数処理前の特急料金から引ききれない分を運賃から差し引き、運賃も端数処理する
+    #[test]
+    fn fixed_amount_after_rate() {
+        let individual_discounts = [
+            IndividualDiscount { rounding_point: Last, ..discount(ExpressFare, Rate(Fraction::percent(90)), 1) },
+            discount(SingleFare, FixedAmount(Amount { value: 6000 }), 2),
+        ];
+        assert_eq!(fare(7850, 0), apply_discounts(fare(8910, 5495), &individual_discounts, &DiscountPolicy::default()));
+    }
+
+    #[rstest]
+    // 割引ごとに重ね方を宣言する : 往復割引だけを 1 つずつ適用し、残りの 2 つを掛け合わせる
+    #[case(Sequential, Multiplicative, Multiplicative, 6120, 4190)]
+    // 往復割引を掛け合わせる割引に含める
+    #[case(Multiplicative, Multiplicative, Multiplicative, 6130, 4190)]
+    // 団体割引 ( 0.9 ) だけを 1 つずつ適用する
+    #[case(Multiplicative, Sequential, Multiplicative, 6120, 4190)]
+    fn stacking_mode_per_discount(
+        #[case] round_trip: StackingMode,
+        #[case] group_90: StackingMode,
+        #[case] group_85: StackingMode,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        let individual_discounts = [
+            IndividualDiscount { stacking_mode: round_trip, ..create_round_trip_discount() },
+            IndividualDiscount { stacking_mode: group_90, ..create_group_discount_under_30(Fraction::percent(90)) },
+            IndividualDiscount {
+                stacking_mode: group_85,
+                priority: 3,
+                ..create_group_discount_under_30(Fraction::percent(85))
+            },
+        ];
+        assert_eq!(
+            fare(train_fare, express_fare),
+            apply_discounts(fare(8910, 5490), &individual_discounts, &DiscountPolicy::default())
+        );
+    }
+
+    #[rstest]
+    // 往復割引の後だけ端数を処理せず、団体割引の後にまとめて処理する
+    #[case(Last, EachStep, 7210, 4940)]
+    #[case(EachStep, EachStep, 7200, 4940)]
+    fn rounding_point_per_discount(
+        #[case] round_trip: RoundingPoint,
+        #[case] group: RoundingPoint,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        let individual_discounts = [
+            IndividualDiscount { rounding_point: round_trip, ..create_round_trip_discount() },
+            IndividualDiscount { rounding_point: group, ..create_group_discount_under_30(Fraction::percent(90)) },
+        ];
+        assert_eq!(
+            fare(train_fare, express_fare),
+            apply_discounts(fare(8910, 5490), &individual_discounts, &DiscountPolicy::default())
+        );
+    }
+
+    #[test]
+    fn stacking_override() {
+        let individual_discounts =
+            [create_round_trip_discount(), create_group_discount_under_30(Fraction::percent(90))];
+        let stacking_override = |name: &str| StackingOverride {
+            name: name.to_string(),
+            stacking_mode: Multiplicative,
+            rounding_point: Last,
+        };
+        let discount_policy = DiscountPolicy {
+            stacking_overrides: vec![stacking_override("往復割引"), stacking_override("団体割引")],
+            ..DiscountPolicy::default()
+        };
+        assert_eq!(
+            fare(7200, 4940),
+            apply_discounts(fare(8910, 5490), &individual_discounts, &DiscountPolicy::default())
+        );
+        assert_eq!(fare(7210, 4940), apply_discounts(fare(8910, 5490), &individual_discounts, &discount_policy));
+    }
+
+    #[test]
+    fn untouched_component_is_not_rounded() {
+        assert_eq!(
+            fare(9000, 5925),
+            apply_discounts(fare(10010, 5925), &[create_round_trip_discount()], &DiscountPolicy::default())
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::rc::Rc;
+
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, UnroundedFare};
+use crate::domain::discount::group_discount::{
+    GroupDiscountUnder30Rule, SchoolGroupDiscountRule, VisitingGroupDiscountRule,
+};
+use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+use crate::domain::discount::round_trip_discount::RoundTripDiscountRule;
+
+// 個別割引の規則
+// 割引を追加する場合は規則を実装して登録するだけでよく、判定、適用、説明のいずれも規則が受け持つ
+// 適用と説明は、割引の対象と方法に従う既定の実装で足りなければ規則ごとに上書きする
+pub trait DiscountRule {
+    // 問い合わせ内容から適用できるかを判定し、適用できる場合は割引を返す
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount>;
+
+    // 判定した割引を端数処理前の運賃と特急料金に適用する ( 端数処理のタイミングは DiscountPolicy が決める )
+    // 割引率を掛け合わせる割引は DiscountPolicy がまとめて適用するため、この関数を用いない
+    fn apply(&self, discount: &IndividualDiscount, fare: &mut UnroundedFare) {
+        fare.apply(discount.target, &discount.discount_method, discount.rounding);
+    }
+
+    // 内訳に表示する説明
+    fn describe(&self, discount: &IndividualDiscount) -> String {
+        let target = match discount.target {
+            DiscountTarget::TrainFare => "運賃",
+            DiscountTarget::ExpressFare => "特急料金",
+            DiscountTarget::SingleFare => "運賃 + 特急料金",
+        };
+        let target = match discount.passenger_target {
+            PassengerTarget::All => target.to_string(),
+            PassengerTarget::Adult => format!("おとな {target}"),
+            PassengerTarget::Child => format!("こども {target}"),
+        };
+        match &discount.discount_method {
+            DiscountMethod::Rate(rate) => format!("{} ( {target} x {rate} )", discount.name),
+            DiscountMethod::FixedAmount(amount) => format!("{} ( {target} - {} 円 )", discount.name, amount.value),
+        }
+    }
+}
+
+impl fmt::Debug for dyn DiscountRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DiscountRule")
+    }
+}
+
+// 判定に用いる割引規則の一覧
+// 既定では JR の割引規則を持ち、会社独自の割引は register で追加する
+pub struct DiscountRuleRegistry {
+    rules: Vec<Rc<dyn DiscountRule>>,
+}
+
+impl DiscountRuleRegistry {
+    pub fn empty() -> Self {
+        Self { rules: vec![] }
+    }
+
+    pub fn register(&mut self, rule: Rc<dyn DiscountRule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn judge(&self, fare_query: &FareQuery) -> Vec<IndividualDiscount> {
+        self.rules.iter().filter_map(|rule| rule.judge(fare_query)).collect()
+    }
+}
+
+impl Default for DiscountRuleRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Rc::new(RoundTripDiscountRule));
+        registry.register(Rc::new(GroupDiscountUnder30Rule));
+        registry.register(Rc::new(SchoolGroupDiscountRule { passenger_target: PassengerTarget::Adult }));
+        registry.register(Rc::new(SchoolGroupDiscountRule { passenger_target: PassengerTarget::Child }));
+        registry.register(Rc::new(VisitingGroupDiscountRule));
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use chrono::NaiveDate;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::reserve_type::ReserveType::RoundTrip;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::discount::discount_policy::{
+        apply_discounts, DiscountMethod, DiscountPolicy, DiscountTarget, RoundingPoint, StackingMode, UnroundedFare,
+    };
+    use crate::domain::discount::discount_rule::{DiscountRule, DiscountRuleRegistry};
+    use crate::domain::discount::individual_discount::{
+        judge_individual_discounts, IndividualDiscount, PassengerTarget,
+    };
+    use crate::domain::discount::round_trip_discount::create_round_trip_discount;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::rounding::Rounding;
+
+    // 会社独自の割引の例
+    // 特急料金を半額にしたうえで、さらに 100 円を差し引く
+    struct StaffDiscountRule;
+
+    impl DiscountRule for StaffDiscountRule {
+        fn judge(&self, _: &FareQuery) -> Option<IndividualDiscount> {
+            Some(IndividualDiscount {
+                rule: Rc::new(StaffDiscountRule),
+                name: "社員割引".to_string(),
+                target: DiscountTarget::ExpressFare,
+                passenger_target: PassengerTarget::All,
+                priority: 3,
+                discount_method: DiscountMethod::Rate(Fraction::percent(50)),
+                rounding: Rounding::FLOOR_TO_TEN,
+                stacking_mode: StackingMode::Sequential,
+                rounding_point: RoundingPoint::EachStep,
+            })
+        }
+
+        fn apply(&self, discount: &IndividualDiscount, fare: &mut UnroundedFare) {
+            fare.apply(discount.target, &discount.discount_method, discount.rounding);
+            fare.apply(discount.target, &DiscountMethod::FixedAmount(Amount { value: 100 }), discount.rounding);
+        }
+
+        fn describe(&self, discount: &IndividualDiscount) -> String {
+            format!("{} ( 特急料金 x 0.5 - 100 円 )", discount.name)
+        }
+    }
+
+    #[test]
+    fn register() {
+        let fare_query = FareQuery {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            reserve_type: RoundTrip,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap() },
+            ..FareQuery::default()
+        };
+
+        let mut registry = DiscountRuleRegistry::default();
+        registry.register(Rc::new(StaffDiscountRule));
+
+        let act = judge_individual_discounts(&fare_query, &registry);
+        assert_eq!(2, act.len());
+        assert_eq!(create_round_trip_discount(), act[0]);
+        assert_eq!("社員割引", act[1].name);
+        assert_eq!("社員割引 ( 特急料金 x 0.5 - 100 円 )", act[1].describe());
+        let fare = (TrainFare { value: Amount { value: 10010 } }, ExpressFare { value: Amount { value: 5490 } });
+        assert_eq!(
+            (TrainFare { value: Amount { value: 10010 } }, ExpressFare { value: Amount { value: 2640 } }),
+            apply_discounts(fare, &act[1..], &DiscountPolicy::default())
+        );
+
+        assert_eq!(0, judge_individual_discounts(&fare_query, &DiscountRuleRegistry::empty()).len());
+    }
+}
@@ -0,0 +1,113 @@
+use std::rc::Rc;
+
+use crate::domain::base::departure_date::Season;
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::group_type::GroupType;
+use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, RoundingPoint, StackingMode};
+use crate::domain::discount::discount_rule::DiscountRule;
+use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// 団体割引が適用される最少の人数
+const GROUP_MIN_PASSENGERS: usize = 8;
+
+pub fn create_group_discount_under_30(discount_rate: Fraction) -> IndividualDiscount {
+    IndividualDiscount {
+        rule: Rc::new(GroupDiscountUnder30Rule),
+        name: "団体割引".to_string(),
+        target: DiscountTarget::SingleFare,
+        passenger_target: PassengerTarget::All,
+        priority: 2,
+        discount_method: DiscountMethod::Rate(discount_rate),
+        rounding: Rounding::FLOOR_TO_TEN,
+        stacking_mode: StackingMode::Sequential,
+        rounding_point: RoundingPoint::EachStep,
+    }
+}
+
+pub fn create_school_group_discount(passenger_target: PassengerTarget, discount_rate: Fraction) -> IndividualDiscount {
+    IndividualDiscount {
+        rule: Rc::new(SchoolGroupDiscountRule { passenger_target }),
+        name: "学生団体割引".to_string(),
+        target: DiscountTarget::TrainFare,
+        passenger_target,
+        priority: 2,
+        discount_method: DiscountMethod::Rate(discount_rate),
+        rounding: Rounding::FLOOR_TO_TEN,
+        stacking_mode: StackingMode::Sequential,
+        rounding_point: RoundingPoint::EachStep,
+    }
+}
+
+pub fn create_visiting_group_discount() -> IndividualDiscount {
+    IndividualDiscount {
+        rule: Rc::new(VisitingGroupDiscountRule),
+        name: "訪日観光団体割引".to_string(),
+        target: DiscountTarget::SingleFare,
+        passenger_target: PassengerTarget::All,
+        priority: 2,
+        discount_method: DiscountMethod::Rate(Fraction::percent(85)),
+        rounding: Rounding::FLOOR_TO_TEN,
+        stacking_mode: StackingMode::Sequential,
+        rounding_point: RoundingPoint::EachStep,
+    }
+}
+
+// 8 名以上 30 名以下の普通団体には繁忙期 1 割引、それ以外 1.5 割引を適用する
+pub struct GroupDiscountUnder30Rule;
+
+impl DiscountRule for GroupDiscountUnder30Rule {
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount> {
+        if fare_query.group_type == GroupType::Ordinary
+            && (GROUP_MIN_PASSENGERS..=30).contains(&fare_query.number_of_passengers.total())
+        {
+            let discount_rate = match fare_query.departure_date.get_season() {
+                Season::Regular => Fraction::percent(85),
+                Season::OffPeak => Fraction::percent(85),
+                Season::Peak => Fraction::percent(90),
+            };
+            Some(create_group_discount_under_30(discount_rate))
+        } else {
+            None
+        }
+    }
+}
+
+// 8 名以上の学生団体には運賃のみ、こどもに 5 割引、学生と教職員に 3 割引を適用する
+pub struct SchoolGroupDiscountRule {
+    pub passenger_target: PassengerTarget,
+}
+
+impl DiscountRule for SchoolGroupDiscountRule {
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount> {
+        let number_of_passengers = &fare_query.number_of_passengers;
+        if fare_query.group_type != GroupType::School || number_of_passengers.total() < GROUP_MIN_PASSENGERS {
+            return None;
+        }
+        match self.passenger_target {
+            PassengerTarget::Adult if 0 < number_of_passengers.adult => {
+                Some(create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70)))
+            }
+            PassengerTarget::Child if 0 < number_of_passengers.child => {
+                Some(create_school_group_discount(PassengerTarget::Child, Fraction::percent(50)))
+            }
+            _ => None,
+        }
+    }
+}
+
+// 8 名以上の訪日観光団体には時期によらず 1.5 割引を適用する
+pub struct VisitingGroupDiscountRule;
+
+impl DiscountRule for VisitingGroupDiscountRule {
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount> {
+        if fare_query.group_type == GroupType::Visiting
+            && GROUP_MIN_PASSENGERS <= fare_query.number_of_passengers.total()
+        {
+            Some(create_visiting_group_discount())
+        } else {
+            None
+        }
+    }
+}
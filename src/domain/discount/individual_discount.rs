@@ -1,114 +1,212 @@
-use crate::domain::base::departure_date::Season;
-use crate::domain::base::number_of_passengers::NumberOfPassengers;
-use crate::domain::base::ride_section::RideSection;
-use crate::domain::discount::individual_discount::IndividualDiscount::{GroupDiscountUnder30, RoundTripDiscount};
-use crate::domain::fare::express_fare::ExpressFare;
-use crate::domain::fare::train_fare::TrainFare;
-
-#[derive(PartialEq, Debug)]
-pub enum IndividualDiscount {
-    RoundTripDiscount,
-    GroupDiscountUnder30 { discount_rate: f32 },
+use std::rc::Rc;
+
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, RoundingPoint, StackingMode};
+use crate::domain::discount::discount_rule::{DiscountRule, DiscountRuleRegistry};
+use crate::fundamental::rounding::Rounding;
+
+// 割引を適用する旅客
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum PassengerTarget {
+    All,
+    Adult,
+    Child,
 }
 
-impl IndividualDiscount {
-    pub fn apply(&self, fare: (TrainFare, ExpressFare)) -> (TrainFare, ExpressFare) {
-        match self {
-            RoundTripDiscount => (TrainFare { value: fare.0.value * 0.9 }, fare.1),
-            GroupDiscountUnder30 { discount_rate } => (
-                TrainFare { value: fare.0.value * *discount_rate },
-                ExpressFare { value: fare.1.value * *discount_rate },
-            ),
-        }
+// 1 人ごとの料金に適用する割引
+// 適用の可否と内容は rule が判定し、適用と説明も rule に委ねる
+#[derive(Clone, Debug)]
+pub struct IndividualDiscount {
+    pub rule: Rc<dyn DiscountRule>,
+    pub name: String,
+    pub target: DiscountTarget,
+    pub passenger_target: PassengerTarget,
+    // 小さいほど先に適用する
+    pub priority: u8,
+    pub discount_method: DiscountMethod,
+    // 割引後の金額の端数処理
+    pub rounding: Rounding,
+    pub stacking_mode: StackingMode,
+    pub rounding_point: RoundingPoint,
+}
+
+// 内容が同じ割引は、判定した規則のインスタンスによらず等しいとみなす
+impl PartialEq for IndividualDiscount {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.target == other.target
+            && self.passenger_target == other.passenger_target
+            && self.priority == other.priority
+            && self.discount_method == other.discount_method
+            && self.rounding == other.rounding
+            && self.stacking_mode == other.stacking_mode
+            && self.rounding_point == other.rounding_point
     }
-    // この作りだと (train * rate + express * rate) はできるが (train + express) * rate ができない
-    // 仕様がはっきりしないので前者で濁すが、要再設計
+}
 
-    // 0.9 を 2 度適用した場合の切り捨てタイミングが不明瞭
-    // 個別切り捨てだと 0.85 -> 0.9 と 0.9 -> 0.85 で結果が変わるため 0.765 を適用する方がよさそう
-    // ほかにどのような割引を考慮するべきかわからないと汎用化できないため、暫定で個別適用とする
+impl IndividualDiscount {
+    pub fn describe(&self) -> String {
+        self.rule.describe(self)
+    }
 }
 
 pub fn judge_individual_discounts(
-    ride_section: &RideSection,
-    number_of_passengers: &NumberOfPassengers,
-    season: &Season,
+    fare_query: &FareQuery,
+    discount_rule_registry: &DiscountRuleRegistry,
 ) -> Vec<IndividualDiscount> {
-    let mut discounts = vec![];
-
-    if 601.0 <= ride_section.get_operation_kilometer().value {
-        discounts.push(RoundTripDiscount)
-    }
-    if (8..=30).contains(&number_of_passengers.total()) {
-        let discount_rate = match season {
-            Season::Regular => 0.85,
-            Season::OffPeak => 0.85,
-            Season::Peak => 0.9,
-        };
-        discounts.push(GroupDiscountUnder30 { discount_rate });
-    }
+    discount_rule_registry.judge(fare_query)
+}
 
-    discounts
+// おとなとこどもで割引が異なる場合があるため、旅客ごとに適用する割引を選ぶ
+pub fn select_individual_discounts(
+    individual_discounts: &[IndividualDiscount],
+    passenger_target: PassengerTarget,
+) -> Vec<IndividualDiscount> {
+    individual_discounts
+        .iter()
+        .filter(|discount| [PassengerTarget::All, passenger_target].contains(&discount.passenger_target))
+        .cloned()
+        .collect()
 }
 
 #[cfg(test)]
+#[allow(clippy::too_many_arguments)]
 mod tests {
-
+    use chrono::NaiveDate;
     use rstest::rstest;
 
-    use crate::domain::base::departure_date::Season;
-    use crate::domain::base::departure_date::Season::*;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::group_type::GroupType;
+    use crate::domain::base::group_type::GroupType::*;
     use crate::domain::base::number_of_passengers::NumberOfPassengers;
+    use crate::domain::base::reserve_type::ReserveType;
+    use crate::domain::base::reserve_type::ReserveType::*;
     use crate::domain::base::ride_section::Station::*;
     use crate::domain::base::ride_section::{RideSection, Station};
-    use crate::domain::discount::individual_discount::IndividualDiscount::{GroupDiscountUnder30, RoundTripDiscount};
-    use crate::domain::discount::individual_discount::{judge_individual_discounts, IndividualDiscount};
+    use crate::domain::discount::discount_policy::{apply_discounts, DiscountPolicy};
+    use crate::domain::discount::discount_rule::DiscountRuleRegistry;
+    use crate::domain::discount::group_discount::{
+        create_group_discount_under_30, create_school_group_discount, create_visiting_group_discount,
+    };
+    use crate::domain::discount::individual_discount::{
+        judge_individual_discounts, select_individual_discounts, IndividualDiscount, PassengerTarget,
+    };
+    use crate::domain::discount::round_trip_discount::create_round_trip_discount;
     use crate::domain::fare::express_fare::ExpressFare;
     use crate::domain::fare::train_fare::TrainFare;
     use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+
+    // 2 月 1 日は通常期、 1 月 10 日は繁忙期、 1 月 20 日は閑散期
+    fn fare_query(
+        departure: Station,
+        arrival: Station,
+        reserve_type: ReserveType,
+        adult: usize,
+        child: usize,
+        m: u32,
+        d: u32,
+    ) -> FareQuery {
+        FareQuery {
+            ride_section: RideSection { departure, arrival, departure_connection: None, arrival_connection: None },
+            reserve_type,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() },
+            number_of_passengers: NumberOfPassengers { adult, child },
+            ..FareQuery::default()
+        }
+    }
 
     #[rstest]
-    #[case(Tokyo, ShinOsaka, 1, 0, Regular)]
-    #[case(Tokyo, ShinOsaka, 31, 0, Regular)]
-    #[case(Tokyo, ShinOsaka, 16, 15, Regular)]
+    #[case(Tokyo, ShinOsaka, RoundTrip, 1, 0, 2, 1)]
+    #[case(Tokyo, ShinOsaka, RoundTrip, 31, 0, 2, 1)]
+    #[case(Tokyo, ShinOsaka, RoundTrip, 16, 15, 2, 1)]
     fn test_create_individual_discounts_no_result(
         #[case] departure: Station,
         #[case] arrival: Station,
+        #[case] reserve_type: ReserveType,
         #[case] adult: usize,
         #[case] child: usize,
-        #[case] season: Season,
+        #[case] m: u32,
+        #[case] d: u32,
     ) {
-        let ride_section = RideSection { departure, arrival };
-        let number_of_passengers = NumberOfPassengers { adult, child };
-        assert_eq!(0, judge_individual_discounts(&ride_section, &number_of_passengers, &season).len());
+        let fare_query = fare_query(departure, arrival, reserve_type, adult, child, m, d);
+        assert_eq!(0, judge_individual_discounts(&fare_query, &DiscountRuleRegistry::default()).len());
     }
 
     #[rstest]
-    #[case(Tokyo, Himeji, 1, 0, Regular, RoundTripDiscount)]
-    #[case(Tokyo, ShinOsaka, 8, 0, Peak, GroupDiscountUnder30 { discount_rate: 0.9 })]
-    #[case(Tokyo, ShinOsaka, 8, 0, Regular, GroupDiscountUnder30 { discount_rate: 0.85 })]
-    #[case(Tokyo, ShinOsaka, 8, 0, OffPeak, GroupDiscountUnder30 { discount_rate: 0.85 })]
-    #[case(Tokyo, ShinOsaka, 4, 4, Regular, GroupDiscountUnder30 { discount_rate: 0.85 })]
-    #[case(Tokyo, ShinOsaka, 15, 15, Regular, GroupDiscountUnder30 { discount_rate: 0.85 })]
+    #[case(Tokyo, Himeji, RoundTrip, 1, 0, 2, 1, create_round_trip_discount())]
+    #[case(Tokyo, Himeji, SingleTrip, 1, 0, 2, 1, create_round_trip_discount())]
+    #[case(Tokyo, ShinOsaka, SingleTrip, 8, 0, 1, 10, create_group_discount_under_30(Fraction::percent(90)))]
+    #[case(Tokyo, ShinOsaka, SingleTrip, 8, 0, 2, 1, create_group_discount_under_30(Fraction::percent(85)))]
+    #[case(Tokyo, ShinOsaka, SingleTrip, 8, 0, 1, 20, create_group_discount_under_30(Fraction::percent(85)))]
+    #[case(Tokyo, ShinOsaka, SingleTrip, 4, 4, 2, 1, create_group_discount_under_30(Fraction::percent(85)))]
+    #[case(Tokyo, ShinOsaka, SingleTrip, 15, 15, 2, 1, create_group_discount_under_30(Fraction::percent(85)))]
     fn test_create_individual_discounts(
         #[case] departure: Station,
         #[case] arrival: Station,
+        #[case] reserve_type: ReserveType,
         #[case] adult: usize,
         #[case] child: usize,
-        #[case] season: Season,
+        #[case] m: u32,
+        #[case] d: u32,
         #[case] exp: IndividualDiscount,
     ) {
-        let ride_section = RideSection { departure, arrival };
-        let number_of_passengers = NumberOfPassengers { adult, child };
-        let act = judge_individual_discounts(&ride_section, &number_of_passengers, &season);
+        let fare_query = fare_query(departure, arrival, reserve_type, adult, child, m, d);
+        let act = judge_individual_discounts(&fare_query, &DiscountRuleRegistry::default());
         assert_eq!(1, act.len());
         assert_eq!(exp, act[0]);
     }
 
     #[rstest]
-    #[case(RoundTripDiscount, 10010, 5920, 9000, 5920)]
-    #[case(GroupDiscountUnder30 { discount_rate: 0.9 }, 8910, 5490, 8010, 4940)]
-    #[case(GroupDiscountUnder30 { discount_rate: 0.85 }, 8910, 5490, 7570, 4660)]
+    // 団体の種類ごとに割引が異なる
+    #[case(Ordinary, 8, 0, 2, 1, vec![create_group_discount_under_30(Fraction::percent(85))])]
+    #[case(Ordinary, 31, 0, 2, 1, vec![])]
+    #[case(School, 7, 0, 2, 1, vec![])]
+    #[case(School, 8, 0, 2, 1, vec![create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70))])]
+    #[case(School, 0, 8, 2, 1, vec![create_school_group_discount(PassengerTarget::Child, Fraction::percent(50))])]
+    #[case(
+        School,
+        2,
+        40,
+        1,
+        10,
+        vec![create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70)), create_school_group_discount(PassengerTarget::Child, Fraction::percent(50))]
+    )]
+    #[case(Visiting, 7, 0, 2, 1, vec![])]
+    #[case(Visiting, 8, 0, 1, 10, vec![create_visiting_group_discount()])]
+    #[case(Visiting, 31, 0, 2, 1, vec![create_visiting_group_discount()])]
+    fn test_create_group_discounts(
+        #[case] group_type: GroupType,
+        #[case] adult: usize,
+        #[case] child: usize,
+        #[case] m: u32,
+        #[case] d: u32,
+        #[case] exp: Vec<IndividualDiscount>,
+    ) {
+        let fare_query = FareQuery { group_type, ..fare_query(Tokyo, ShinOsaka, SingleTrip, adult, child, m, d) };
+        assert_eq!(exp, judge_individual_discounts(&fare_query, &DiscountRuleRegistry::default()));
+    }
+
+    #[test]
+    fn select() {
+        let adult_discount = create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70));
+        let child_discount = create_school_group_discount(PassengerTarget::Child, Fraction::percent(50));
+        let individual_discounts = [create_round_trip_discount(), adult_discount.clone(), child_discount.clone()];
+        assert_eq!(
+            vec![create_round_trip_discount(), adult_discount],
+            select_individual_discounts(&individual_discounts, PassengerTarget::Adult)
+        );
+        assert_eq!(
+            vec![create_round_trip_discount(), child_discount],
+            select_individual_discounts(&individual_discounts, PassengerTarget::Child)
+        );
+    }
+
+    #[rstest]
+    #[case(create_round_trip_discount(), 10010, 5920, 9000, 5920)]
+    #[case(create_group_discount_under_30(Fraction::percent(90)), 8910, 5490, 8010, 4940)]
+    #[case(create_group_discount_under_30(Fraction::percent(85)), 8910, 5490, 7570, 4660)]
     fn apply(
         #[case] sut: IndividualDiscount,
         #[case] train_fare: u64,
@@ -122,6 +220,17 @@ mod tests {
             TrainFare { value: Amount { value: applied_train_fare } },
             ExpressFare { value: Amount { value: applied_express_fare } },
         );
-        assert_eq!(exp, sut.apply(fare));
+        assert_eq!(exp, apply_discounts(fare, &[sut], &DiscountPolicy::default()));
+    }
+
+    #[rstest]
+    #[case(create_round_trip_discount(), "往復割引 ( 運賃 x 0.9 )")]
+    #[case(create_group_discount_under_30(Fraction::percent(85)), "団体割引 ( 運賃 + 特急料金 x 0.85 )")]
+    #[case(
+        create_school_group_discount(PassengerTarget::Child, Fraction::percent(50)),
+        "学生団体割引 ( こども 運賃 x 0.5 )"
+    )]
+    fn describe(#[case] sut: IndividualDiscount, #[case] exp: &str) {
+        assert_eq!(exp, sut.describe());
     }
 }
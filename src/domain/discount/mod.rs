@@ -1,2 +1,7 @@
+pub mod discount_policy;
+pub mod discount_rule;
+pub mod group_discount;
 pub mod individual_discount;
 pub mod overall_discount;
+pub mod promotion;
+pub mod round_trip_discount;
@@ -1,17 +1,82 @@
-use std::cmp::max;
+use std::cmp::{max, min};
+use std::num::NonZeroUsize;
 
+use crate::domain::base::group_type::GroupType;
 use crate::domain::base::number_of_passengers::NumberOfPassengers;
-use crate::domain::discount::overall_discount::OverallDiscount::GroupDiscountMore31;
+use crate::domain::discount::overall_discount::OverallDiscount::LargeGroupDiscount;
 
+// 無料になる旅客がいる団体の人数の設定
+// 30 名以下の団体割引 ( GroupDiscountUnder30Rule ) の人数とは独立に設定する
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct GroupFreePassengerPolicy {
+    // 無料になる旅客がいる最少の人数
+    pub min_passengers: usize,
+    // この人数ごとに 1 名が無料になる ( 0 名ごとは定義できない )
+    pub passengers_per_free_passenger: NonZeroUsize,
+}
+
+impl Default for GroupFreePassengerPolicy {
+    // 31 名以上の団体は 50 名ごとに 1 名が無料になる
+    fn default() -> Self {
+        Self { min_passengers: 31, passengers_per_free_passenger: NonZeroUsize::new(50).unwrap() }
+    }
+}
+
+// free_passengers は無料になる旅客の内訳
 #[derive(PartialEq, Debug)]
 pub enum OverallDiscount {
-    GroupDiscountMore31 { free_count: usize },
+    LargeGroupDiscount { min_passengers: usize, free_passengers: NumberOfPassengers },
 }
 
-pub fn judge_overall_discount(number_of_passengers: &NumberOfPassengers) -> Option<OverallDiscount> {
-    if 31 <= number_of_passengers.total() {
-        let free_count = max(number_of_passengers.total() / 50, 1);
-        Some(GroupDiscountMore31 { free_count })
+impl OverallDiscount {
+    pub fn get_free_passengers(&self) -> NumberOfPassengers {
+        match self {
+            LargeGroupDiscount { free_passengers, .. } => *free_passengers,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            LargeGroupDiscount { min_passengers, free_passengers } => {
+                let free = match (free_passengers.adult, free_passengers.child) {
+                    (adult, 0) => format!("おとな {adult} 名"),
+                    (0, child) => format!("こども {child} 名"),
+                    (adult, child) => format!("おとな {adult} 名 こども {child} 名"),
+                };
+                format!("団体割引 ( {min_passengers} 名以上 ) 無料 {free}")
+            }
+        }
+    }
+
+    // 無料になる旅客をどのように選んだかを説明する
+    pub fn explain(&self) -> String {
+        match self {
+            LargeGroupDiscount { free_passengers, .. } => match free_passengers.child {
+                0 => "団体割引で無料になる旅客は料金の高いおとなから選びます".to_string(),
+                _ => "団体割引で無料になるおとなが足りないため、残りはこどもから選びます".to_string(),
+            },
+        }
+    }
+}
+
+// 設定した人数 ( 既定は 31 名 ) 以上の普通団体と訪日観光団体は、こどもを含めた人数の設定した人数 ( 既定は 50 名 ) ごとに
+// 1 名 ( 最低 1 名 ) が無料になる
+// 無料になる旅客は料金の高いおとなから選び、おとなが足りない分はこどもから選ぶ
+// 学生団体は割引率が大きい代わりに無料になる人数はない
+pub fn judge_overall_discount(
+    group_type: &GroupType,
+    number_of_passengers: &NumberOfPassengers,
+    group_free_passenger_policy: &GroupFreePassengerPolicy,
+) -> Option<OverallDiscount> {
+    let GroupFreePassengerPolicy { min_passengers, passengers_per_free_passenger } = *group_free_passenger_policy;
+    if *group_type != GroupType::School && min_passengers <= number_of_passengers.total() {
+        let free_count = max(number_of_passengers.total() / passengers_per_free_passenger.get(), 1);
+        let free_adult = min(free_count, number_of_passengers.adult);
+        let free_child = min(free_count - free_adult, number_of_passengers.child);
+        Some(LargeGroupDiscount {
+            min_passengers,
+            free_passengers: NumberOfPassengers { adult: free_adult, child: free_child },
+        })
     } else {
         None
     }
@@ -19,30 +84,115 @@ pub fn judge_overall_discount(number_of_passengers: &NumberOfPassengers) -> Opti
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use rstest::rstest;
 
+    use crate::domain::base::group_type::GroupType;
+    use crate::domain::base::group_type::GroupType::*;
     use crate::domain::base::number_of_passengers::NumberOfPassengers;
-    use crate::domain::discount::overall_discount::OverallDiscount::*;
-    use crate::domain::discount::overall_discount::{judge_overall_discount, OverallDiscount};
+    use crate::domain::discount::overall_discount::{judge_overall_discount, GroupFreePassengerPolicy};
 
     #[rstest]
-    #[case(1, 0)]
-    #[case(0, 1)]
-    #[case(7, 0)]
-    #[case(0, 7)]
-    fn test_create_overall_discounts_no_result(#[case] adult: usize, #[case] child: usize) {
+    #[case(Ordinary, 1, 0)]
+    #[case(Ordinary, 0, 1)]
+    #[case(Ordinary, 7, 0)]
+    #[case(Ordinary, 0, 7)]
+    #[case(Ordinary, 30, 0)]
+    #[case(Visiting, 30, 0)]
+    #[case(School, 31, 0)]
+    #[case(School, 10, 90)]
+    fn test_create_overall_discounts_no_result(
+        #[case] group_type: GroupType,
+        #[case] adult: usize,
+        #[case] child: usize,
+    ) {
         let number_of_passengers = NumberOfPassengers { adult, child };
-        assert_eq!(None, judge_overall_discount(&number_of_passengers));
+        assert_eq!(
+            None,
+            judge_overall_discount(&group_type, &number_of_passengers, &GroupFreePassengerPolicy::default())
+        );
     }
 
     #[rstest]
-    #[case(31, 0, GroupDiscountMore31 { free_count: 1 })]
-    #[case(50, 0, GroupDiscountMore31 { free_count: 1 })]
-    #[case(51, 0, GroupDiscountMore31 { free_count: 1 })]
-    #[case(99, 0, GroupDiscountMore31 { free_count: 1 })]
-    #[case(100, 0, GroupDiscountMore31 { free_count: 2 })]
-    fn test_create_overall_discounts(#[case] adult: usize, #[case] child: usize, #[case] exp: OverallDiscount) {
+    #[case(Ordinary, 31, 0, 1, 0)]
+    #[case(Ordinary, 50, 0, 1, 0)]
+    #[case(Ordinary, 51, 0, 1, 0)]
+    #[case(Ordinary, 99, 0, 1, 0)]
+    #[case(Ordinary, 100, 0, 2, 0)]
+    #[case(Visiting, 31, 0, 1, 0)]
+    #[case(Visiting, 100, 0, 2, 0)]
+    // こどもも人数に含める
+    #[case(Ordinary, 20, 11, 1, 0)]
+    #[case(Ordinary, 50, 50, 2, 0)]
+    // おとなが足りない分はこどもを無料にする
+    #[case(Ordinary, 1, 99, 1, 1)]
+    #[case(Ordinary, 0, 31, 0, 1)]
+    #[case(Ordinary, 0, 100, 0, 2)]
+    fn test_create_overall_discounts(
+        #[case] group_type: GroupType,
+        #[case] adult: usize,
+        #[case] child: usize,
+        #[case] free_adult: usize,
+        #[case] free_child: usize,
+    ) {
         let number_of_passengers = NumberOfPassengers { adult, child };
-        assert_eq!(exp, judge_overall_discount(&number_of_passengers).unwrap());
+        assert_eq!(
+            NumberOfPassengers { adult: free_adult, child: free_child },
+            judge_overall_discount(&group_type, &number_of_passengers, &GroupFreePassengerPolicy::default())
+                .unwrap()
+                .get_free_passengers()
+        );
+    }
+
+    // 無料になる旅客がいる人数と、1 名が無料になる人数は設定で変えられる
+    #[rstest]
+    #[case(20, 0, None)]
+    #[case(21, 0, Some(1))]
+    #[case(39, 0, Some(1))]
+    #[case(40, 0, Some(2))]
+    #[case(20, 20, Some(2))]
+    fn configured_policy(#[case] adult: usize, #[case] child: usize, #[case] exp: Option<usize>) {
+        let policy = GroupFreePassengerPolicy {
+            min_passengers: 21,
+            passengers_per_free_passenger: NonZeroUsize::new(20).unwrap(),
+        };
+        let act = judge_overall_discount(&Ordinary, &NumberOfPassengers { adult, child }, &policy);
+        assert_eq!(exp, act.map(|discount| discount.get_free_passengers().total()));
+    }
+
+    #[test]
+    fn describe_configured_policy() {
+        let policy = GroupFreePassengerPolicy {
+            min_passengers: 21,
+            passengers_per_free_passenger: NonZeroUsize::new(20).unwrap(),
+        };
+        let sut = judge_overall_discount(&Ordinary, &NumberOfPassengers { adult: 21, child: 0 }, &policy).unwrap();
+        assert_eq!("団体割引 ( 21 名以上 ) 無料 おとな 1 名", sut.describe());
+    }
+
+    #[rstest]
+    #[case(31, 0, "団体割引 ( 31 名以上 ) 無料 おとな 1 名", "団体割引で無料になる旅客は料金の高いおとなから選びます")]
+    #[case(
+        0,
+        31,
+        "団体割引 ( 31 名以上 ) 無料 こども 1 名",
+        "団体割引で無料になるおとなが足りないため、残りはこどもから選びます"
+    )]
+    #[case(
+        1,
+        99,
+        "団体割引 ( 31 名以上 ) 無料 おとな 1 名 こども 1 名",
+        "団体割引で無料になるおとなが足りないため、残りはこどもから選びます"
+    )]
+    fn describe(#[case] adult: usize, #[case] child: usize, #[case] description: &str, #[case] explanation: &str) {
+        let sut = judge_overall_discount(
+            &Ordinary,
+            &NumberOfPassengers { adult, child },
+            &GroupFreePassengerPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(description, sut.describe());
+        assert_eq!(explanation, sut.explain());
     }
 }
@@ -0,0 +1,332 @@
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use chrono::NaiveDate;
+
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::number_of_passengers::NumberOfPassengers;
+use crate::domain::base::ride_section::Station;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, RoundingPoint, StackingMode};
+use crate::domain::discount::discount_rule::DiscountRule;
+use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// キャンペーン割引の定義
+// 区間、列車、座席種別は空の場合に制限しない
+// 定額の割引は、こどもには半額をキャンペーンごとの端数処理 ( rounding ) で丸めて差し引く
+#[derive(Clone, Debug)]
+pub struct Promotion {
+    pub code: String,
+    pub name: String,
+    pub booking_period: RangeInclusive<NaiveDate>,
+    pub travel_period: RangeInclusive<NaiveDate>,
+    pub station_pairs: Vec<(Station, Station)>,
+    pub trains: Vec<Train>,
+    pub seat_types: Vec<SeatType>,
+    pub target: DiscountTarget,
+    pub discount_method: DiscountMethod,
+    pub rounding: Rounding,
+    pub stacking_mode: StackingMode,
+    pub rounding_point: RoundingPoint,
+    pub max_uses: usize,
+    pub used_count: usize,
+}
+
+impl Promotion {
+    // 利用数は乗車人数分を消費する
+    pub fn consume(&mut self, number_of_passengers: &NumberOfPassengers) -> Result<(), PromotionRejection> {
+        if self.max_uses < self.used_count + number_of_passengers.total() {
+            return Err(PromotionRejection::UsageLimitReached);
+        }
+        self.used_count += number_of_passengers.total();
+        Ok(())
+    }
+
+    fn judge_eligibility(&self, fare_query: &FareQuery) -> Result<(), PromotionRejection> {
+        if !self.booking_period.contains(&fare_query.booking_date.value) {
+            return Err(PromotionRejection::OutsideBookingPeriod);
+        }
+        if !self.travel_period.contains(&fare_query.departure_date.value) {
+            return Err(PromotionRejection::OutsideTravelPeriod);
+        }
+
+        let (departure, arrival) = fare_query.ride_section.get_station_pair();
+        if !self.station_pairs.is_empty() && !self.station_pairs.contains(&(*departure, *arrival)) {
+            return Err(PromotionRejection::IneligibleRideSection);
+        }
+        if !self.trains.is_empty() && !self.trains.contains(&fare_query.train) {
+            return Err(PromotionRejection::IneligibleTrain);
+        }
+        if !self.seat_types.is_empty() && !self.seat_types.contains(&fare_query.seat_type) {
+            return Err(PromotionRejection::IneligibleSeatType);
+        }
+        if self.max_uses < self.used_count + fare_query.number_of_passengers.total() {
+            return Err(PromotionRejection::UsageLimitReached);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum PromotionRejection {
+    UnknownCode,
+    OutsideBookingPeriod,
+    OutsideTravelPeriod,
+    IneligibleRideSection,
+    IneligibleTrain,
+    IneligibleSeatType,
+    UsageLimitReached,
+}
+
+impl PromotionRejection {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PromotionRejection::UnknownCode => "存在しないコードです",
+            PromotionRejection::OutsideBookingPeriod => "予約日が対象期間外です",
+            PromotionRejection::OutsideTravelPeriod => "乗車日が対象期間外です",
+            PromotionRejection::IneligibleRideSection => "対象外の区間です",
+            PromotionRejection::IneligibleTrain => "対象外の列車です",
+            PromotionRejection::IneligibleSeatType => "対象外の座席種別です",
+            PromotionRejection::UsageLimitReached => "利用可能数が乗車人数に足りません",
+        }
+    }
+}
+
+// 個別割引の後に適用する
+const PROMOTION_PRIORITY: u8 = 3;
+
+// 指定されたコードのキャンペーン割引を、個別割引と同じく割引規則として登録する
+// 割合の割引はおとなとこどもに共通、定額の割引はおとなとこどもで差し引く額が異なるため旅客ごとに分ける
+pub struct PromotionRule {
+    pub promotion: Promotion,
+    pub passenger_target: PassengerTarget,
+}
+
+impl DiscountRule for PromotionRule {
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount> {
+        self.promotion.judge_eligibility(fare_query).ok()?;
+        let number_of_passengers = &fare_query.number_of_passengers;
+        let count = match self.passenger_target {
+            PassengerTarget::All => number_of_passengers.total(),
+            PassengerTarget::Adult => number_of_passengers.adult,
+            PassengerTarget::Child => number_of_passengers.child,
+        };
+        if count == 0 {
+            return None;
+        }
+        let discount_method = match (&self.promotion.discount_method, self.passenger_target) {
+            (DiscountMethod::FixedAmount(amount), PassengerTarget::Child) => {
+                DiscountMethod::FixedAmount(amount.multiply(&Fraction::new(1, 2), &self.promotion.rounding))
+            }
+            (discount_method, _) => discount_method.clone(),
+        };
+        Some(IndividualDiscount {
+            rule: Rc::new(PromotionRule { promotion: self.promotion.clone(), passenger_target: self.passenger_target }),
+            name: self.promotion.name.clone(),
+            target: self.promotion.target,
+            passenger_target: self.passenger_target,
+            priority: PROMOTION_PRIORITY,
+            discount_method,
+            rounding: self.promotion.rounding,
+            stacking_mode: self.promotion.stacking_mode,
+            rounding_point: self.promotion.rounding_point,
+        })
+    }
+}
+
+// コードに該当するキャンペーン割引を問い合わせに適用できるか判定し、登録する割引規則を返す
+// 適用できない場合は理由を返す
+pub fn judge_promotion(
+    code: &str,
+    promotions: &[Promotion],
+    fare_query: &FareQuery,
+) -> Result<Vec<PromotionRule>, PromotionRejection> {
+    let promotion =
+        promotions.iter().find(|promotion| promotion.code == code).ok_or(PromotionRejection::UnknownCode)?;
+    promotion.judge_eligibility(fare_query)?;
+
+    let passenger_targets = match promotion.discount_method {
+        DiscountMethod::Rate(_) => vec![PassengerTarget::All],
+        DiscountMethod::FixedAmount(_) => vec![PassengerTarget::Adult, PassengerTarget::Child],
+    };
+    Ok(passenger_targets
+        .into_iter()
+        .map(|passenger_target| PromotionRule { promotion: promotion.clone(), passenger_target })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+mod tests {
+    use std::rc::Rc;
+
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::booking_date::BookingDate;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::number_of_passengers::NumberOfPassengers;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, RoundingPoint, StackingMode};
+    use crate::domain::discount::discount_rule::DiscountRuleRegistry;
+    use crate::domain::discount::individual_discount::{
+        judge_individual_discounts, IndividualDiscount, PassengerTarget,
+    };
+    use crate::domain::discount::promotion::PromotionRejection::*;
+    use crate::domain::discount::promotion::{judge_promotion, Promotion, PromotionRejection, PromotionRule};
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::rounding::Rounding;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+
+    fn promotions() -> Vec<Promotion> {
+        vec![Promotion {
+            code: "EXHAYA".to_string(),
+            name: "EX早特".to_string(),
+            booking_period: date(1, 1)..=date(1, 31),
+            travel_period: date(2, 1)..=date(2, 29),
+            station_pairs: vec![(Tokyo, ShinOsaka)],
+            trains: vec![Nozomi],
+            seat_types: vec![Reserved],
+            target: DiscountTarget::ExpressFare,
+            discount_method: DiscountMethod::FixedAmount(Amount { value: 1000 }),
+            rounding: Rounding::FLOOR_TO_TEN,
+            stacking_mode: StackingMode::Sequential,
+            rounding_point: RoundingPoint::EachStep,
+            max_uses: 10,
+            used_count: 8,
+        }]
+    }
+
+    fn fare_query(
+        departure: Station,
+        train: Train,
+        seat_type: SeatType,
+        booking_date: NaiveDate,
+        departure_date: NaiveDate,
+        adult: usize,
+    ) -> FareQuery {
+        FareQuery {
+            ride_section: RideSection {
+                departure,
+                arrival: ShinOsaka,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train,
+            seat_type,
+            departure_date: DepartureDate { value: departure_date },
+            number_of_passengers: NumberOfPassengers { adult, child: 0 },
+            booking_date: BookingDate { value: booking_date },
+            ..FareQuery::default()
+        }
+    }
+
+    fn discount(passenger_target: PassengerTarget, discount_method: DiscountMethod) -> IndividualDiscount {
+        IndividualDiscount {
+            rule: Rc::new(PromotionRule { promotion: promotions().remove(0), passenger_target }),
+            name: "EX早特".to_string(),
+            target: DiscountTarget::ExpressFare,
+            passenger_target,
+            priority: 3,
+            discount_method,
+            rounding: Rounding::FLOOR_TO_TEN,
+            stacking_mode: StackingMode::Sequential,
+            rounding_point: RoundingPoint::EachStep,
+        }
+    }
+
+    #[rstest]
+    // 定額の割引は、こどもには半額を差し引く
+    #[case(
+        DiscountMethod::FixedAmount(Amount { value: 1000 }),
+        1,
+        1,
+        vec![
+            discount(PassengerTarget::Adult, DiscountMethod::FixedAmount(Amount { value: 1000 })),
+            discount(PassengerTarget::Child, DiscountMethod::FixedAmount(Amount { value: 500 })),
+        ]
+    )]
+    #[case(
+        DiscountMethod::FixedAmount(Amount { value: 1010 }),
+        0,
+        2,
+        vec![discount(PassengerTarget::Child, DiscountMethod::FixedAmount(Amount { value: 500 }))]
+    )]
+    #[case(
+        DiscountMethod::FixedAmount(Amount { value: 1000 }),
+        2,
+        0,
+        vec![discount(PassengerTarget::Adult, DiscountMethod::FixedAmount(Amount { value: 1000 }))]
+    )]
+    // 割合の割引はおとなとこどもに共通
+    #[case(
+        DiscountMethod::Rate(Fraction::percent(80)),
+        1,
+        1,
+        vec![discount(PassengerTarget::All, DiscountMethod::Rate(Fraction::percent(80)))]
+    )]
+    fn accepted(
+        #[case] discount_method: DiscountMethod,
+        #[case] adult: usize,
+        #[case] child: usize,
+        #[case] exp: Vec<IndividualDiscount>,
+    ) {
+        let promotions = vec![Promotion { discount_method, ..promotions().remove(0) }];
+        let fare_query = FareQuery {
+            number_of_passengers: NumberOfPassengers { adult, child },
+            ..fare_query(Tokyo, Nozomi, Reserved, date(1, 31), date(2, 1), 0)
+        };
+
+        // 個別割引と同じく割引規則の一覧に登録して判定する
+        let mut registry = DiscountRuleRegistry::empty();
+        for rule in judge_promotion("EXHAYA", &promotions, &fare_query).unwrap() {
+            registry.register(Rc::new(rule));
+        }
+        assert_eq!(exp, judge_individual_discounts(&fare_query, &registry));
+    }
+
+    #[rstest]
+    #[case("EXHAYA", Tokyo, Nozomi, Reserved, date(2, 1), date(2, 1), 1, OutsideBookingPeriod)]
+    #[case("EXHAYA", Tokyo, Nozomi, Reserved, date(1, 1), date(3, 1), 1, OutsideTravelPeriod)]
+    #[case("EXHAYA", Himeji, Nozomi, Reserved, date(1, 1), date(2, 1), 1, IneligibleRideSection)]
+    #[case("EXHAYA", Tokyo, Hikari, Reserved, date(1, 1), date(2, 1), 1, IneligibleTrain)]
+    #[case("EXHAYA", Tokyo, Nozomi, Free, date(1, 1), date(2, 1), 1, IneligibleSeatType)]
+    #[case("EXHAYA", Tokyo, Nozomi, Reserved, date(1, 1), date(2, 1), 3, UsageLimitReached)]
+    #[case("UNKNOWN", Tokyo, Nozomi, Reserved, date(1, 1), date(2, 1), 1, UnknownCode)]
+    fn rejected(
+        #[case] code: &str,
+        #[case] departure: Station,
+        #[case] train: Train,
+        #[case] seat_type: SeatType,
+        #[case] booking_date: NaiveDate,
+        #[case] departure_date: NaiveDate,
+        #[case] adult: usize,
+        #[case] exp: PromotionRejection,
+    ) {
+        let fare_query = fare_query(departure, train, seat_type, booking_date, departure_date, adult);
+        assert_eq!(Some(exp), judge_promotion(code, &promotions(), &fare_query).err());
+    }
+
+    #[test]
+    fn consume() {
+        let mut promotion = promotions().remove(0);
+        assert_eq!(Ok(()), promotion.consume(&NumberOfPassengers { adult: 1, child: 1 }));
+        assert_eq!(10, promotion.used_count);
+        assert_eq!(Err(UsageLimitReached), promotion.consume(&NumberOfPassengers { adult: 1, child: 0 }));
+        assert_eq!(10, promotion.used_count);
+    }
+}
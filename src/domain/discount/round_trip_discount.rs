@@ -0,0 +1,39 @@
+use std::rc::Rc;
+
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::discount::discount_policy::{DiscountMethod, DiscountTarget, RoundingPoint, StackingMode};
+use crate::domain::discount::discount_rule::DiscountRule;
+use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// 往復割引が適用される最短の営業キロ
+pub const ROUND_TRIP_DISCOUNT_MIN_KILOMETER: f64 = 601.0;
+
+pub fn create_round_trip_discount() -> IndividualDiscount {
+    IndividualDiscount {
+        rule: Rc::new(RoundTripDiscountRule),
+        name: "往復割引".to_string(),
+        target: DiscountTarget::TrainFare,
+        passenger_target: PassengerTarget::All,
+        priority: 1,
+        discount_method: DiscountMethod::Rate(Fraction::percent(90)),
+        rounding: Rounding::FLOOR_TO_TEN,
+        stacking_mode: StackingMode::Sequential,
+        rounding_point: RoundingPoint::EachStep,
+    }
+}
+
+// 営業キロが 601 km 以上の区間には運賃に 1 割引を適用する
+// 特定都区市内を適用する場合は中心駅からの営業キロで判定する
+pub struct RoundTripDiscountRule;
+
+impl DiscountRule for RoundTripDiscountRule {
+    fn judge(&self, fare_query: &FareQuery) -> Option<IndividualDiscount> {
+        if ROUND_TRIP_DISCOUNT_MIN_KILOMETER <= fare_query.ride_section.get_fare_kilometer().value {
+            Some(create_round_trip_discount())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, bail};
+
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::ride_section::{RideSection, Station};
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::fare::express_fare::{calc_express_fare, ExpressFare};
+use crate::fundamental::amount::Amount;
+
+// スマートEX は指定席の特急料金から 200 円を割り引く
+const SMART_EX_DISCOUNT: Amount = Amount { value: 200 };
+
+// EX サービスの商品ごとの特急料金
+// スマートEX は通常の特急料金と同じく季節によって変動する
+// EX予約 の指定席は通年同額の会員価格で、自由席は通常の特急料金と同じ
+// 会員価格の設定がない区間と列車は EX予約 の対象外とする
+pub fn calc_ex_express_fare(
+    ex_product: &ExProduct,
+    train: &Train,
+    seat_type: &SeatType,
+    ride_section: &RideSection,
+    departure_date: &DepartureDate,
+) -> anyhow::Result<ExpressFare> {
+    judge_ex_coverage(ex_product, ride_section)?;
+    match (ex_product, seat_type) {
+        (ExProduct::SmartEx, SeatType::Reserved) => Ok(ExpressFare {
+            value: calc_express_fare(train, seat_type, ride_section, departure_date)?.value - SMART_EX_DISCOUNT,
+        }),
+        (ExProduct::ExReservation, SeatType::Reserved) => {
+            let value = create_ex_reservation_express_fare(train, ride_section)
+                .ok_or_else(|| anyhow!("{} の対象外区間です", ex_product.describe()))?;
+            Ok(ExpressFare { value })
+        }
+        (_, SeatType::Free) => calc_express_fare(train, seat_type, ride_section, departure_date),
+    }
+}
+
+// EX サービスは新幹線の駅の間だけを予約でき、在来線で乗り継ぐ区間には利用できない
+pub fn judge_ex_coverage(ex_product: &ExProduct, ride_section: &RideSection) -> anyhow::Result<()> {
+    if ride_section.has_connections() {
+        bail!("{} は在来線で乗り継ぐ区間には利用できません", ex_product.describe());
+    }
+    Ok(())
+}
+
+fn create_ex_reservation_express_fare(train: &Train, ride_section: &RideSection) -> Option<Amount> {
+    match (ride_section.get_station_pair(), train) {
+        ((Station::Tokyo, Station::ShinOsaka), Train::Hikari) => Some(Amount { value: 4710 }),
+        ((Station::Tokyo, Station::ShinOsaka), Train::Nozomi) => Some(Amount { value: 4960 }),
+        ((Station::Tokyo, Station::Himeji), Train::Hikari) => Some(Amount { value: 5150 }),
+        ((Station::Tokyo, Station::Himeji), Train::Nozomi) => Some(Amount { value: 5660 }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ex_product::ExProduct;
+    use crate::domain::base::ex_product::ExProduct::*;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::fare::ex_express_fare::calc_ex_express_fare;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::fundamental::amount::Amount;
+
+    #[rstest]
+    // スマートEX は季節による変動の後に 200 円を割り引く
+    #[case(SmartEx, Nozomi, Reserved, Tokyo, ShinOsaka, 1, 15, 5610)]
+    #[case(SmartEx, Nozomi, Reserved, ShinOsaka, Tokyo, 1, 20, 5410)]
+    #[case(SmartEx, Hikari, Reserved, Tokyo, Himeji, 1, 10, 5920)]
+    // スマートEX の自由席は割り引かない
+    #[case(SmartEx, Nozomi, Free, Tokyo, ShinOsaka, 1, 15, 4960)]
+    // EX予約 は通年同額
+    #[case(ExReservation, Nozomi, Reserved, Tokyo, ShinOsaka, 1, 15, 4960)]
+    #[case(ExReservation, Nozomi, Reserved, Tokyo, ShinOsaka, 1, 20, 4960)]
+    #[case(ExReservation, Nozomi, Reserved, ShinOsaka, Tokyo, 1, 10, 4960)]
+    #[case(ExReservation, Hikari, Reserved, Tokyo, ShinOsaka, 1, 15, 4710)]
+    #[case(ExReservation, Hikari, Reserved, Himeji, Tokyo, 1, 10, 5150)]
+    #[case(ExReservation, Nozomi, Reserved, Tokyo, Himeji, 1, 15, 5660)]
+    // EX予約 の自由席は通常の特急料金
+    #[case(ExReservation, Hikari, Free, Tokyo, Himeji, 1, 10, 5390)]
+    fn test_calc_ex_express_fare(
+        #[case] ex_product: ExProduct,
+        #[case] train: Train,
+        #[case] seat_type: SeatType,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] m: u32,
+        #[case] d: u32,
+        #[case] exp: u64,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
+        assert_eq!(
+            ExpressFare { value: Amount { value: exp } },
+            calc_ex_express_fare(&ex_product, &train, &seat_type, &ride_section, &departure_date).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(Nozomi, Tokyo, Hakata)]
+    #[case(Mizuho, ShinOsaka, KagoshimaChuo)]
+    fn ex_reservation_not_covered(#[case] train: Train, #[case] departure: Station, #[case] arrival: Station) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap() };
+        let act = calc_ex_express_fare(&ExReservation, &train, &Reserved, &ride_section, &departure_date);
+        assert_eq!("EX予約 の対象外区間です", act.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    #[case(SmartEx, "スマートEX は在来線で乗り継ぐ区間には利用できません")]
+    #[case(ExReservation, "EX予約 は在来線で乗り継ぐ区間には利用できません")]
+    fn with_connections(#[case] ex_product: ExProduct, #[case] exp: &str) {
+        let ride_section = RideSection {
+            departure: Tokyo,
+            arrival: Himeji,
+            departure_connection: None,
+            arrival_connection: Some(ConventionalStation::Kakogawa),
+        };
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap() };
+        let act = calc_ex_express_fare(&ex_product, &Nozomi, &Reserved, &ride_section, &departure_date);
+        assert_eq!(exp, act.unwrap_err().to_string());
+    }
+}
@@ -1,36 +1,212 @@
 use crate::domain::base::departure_date::{DepartureDate, Season};
+use crate::domain::base::operator::Operator;
 use crate::domain::base::ride_section::{RideSection, Station};
 use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::shinkansen_line::{split_by_line, LineSection, ShinkansenLine};
 use crate::domain::base::train::Train;
 use crate::fundamental::amount::Amount;
+use crate::fundamental::rounding::Rounding;
 
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct ExpressFare {
     pub value: Amount,
 }
 
+// 割合を掛けた特急料金 ( こどもの特急料金など ) は 10 円未満を切り捨てる
+pub const EXPRESS_FARE_ROUNDING: Rounding = Rounding::FLOOR_TO_TEN;
+
+// 指定席特急料金の通常期との差額
+// こどもの特急料金は通常期の料金を折半してから同じ額を加減する
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum SeasonAdjustment {
+    Unadjusted,
+    Increase(Amount),
+    Decrease(Amount),
+}
+
+impl SeasonAdjustment {
+    pub fn apply(&self, amount: Amount) -> Amount {
+        match self {
+            Self::Unadjusted => amount,
+            Self::Increase(adjustment) => amount + adjustment.clone(),
+            Self::Decrease(adjustment) => amount - adjustment.clone(),
+        }
+    }
+
+    // 加減した料金から通常期の料金を求める
+    pub fn revert(&self, amount: Amount) -> Amount {
+        match self {
+            Self::Unadjusted => amount,
+            Self::Increase(adjustment) => amount - adjustment.clone(),
+            Self::Decrease(adjustment) => amount + adjustment.clone(),
+        }
+    }
+}
+
+pub fn judge_season_adjustment(seat_type: &SeatType, departure_date: &DepartureDate) -> SeasonAdjustment {
+    match (seat_type, departure_date.get_season()) {
+        (SeatType::Reserved, Season::Regular) => SeasonAdjustment::Unadjusted,
+        (SeatType::Reserved, Season::OffPeak) => SeasonAdjustment::Decrease(Amount { value: 200 }),
+        (SeatType::Reserved, Season::Peak) => SeasonAdjustment::Increase(Amount { value: 200 }),
+        (SeatType::Free, _) => SeasonAdjustment::Unadjusted,
+    }
+}
+
+// 路線ごとの駅の組み合わせに対するひかり ( さくら ) 指定席特急料金 ( 通常期 ) と、のぞみ ( みずほ ) の加算額
+pub struct LineExpressFareEntry {
+    pub line: ShinkansenLine,
+    pub station_pair: (Station, Station),
+    pub reserved_hikari_fare: Amount,
+    pub nozomi_addition: Amount,
+}
+
+pub fn get_line_express_fare_table() -> Vec<LineExpressFareEntry> {
+    let entry = |line, station_pair, reserved_hikari_fare, nozomi_addition| LineExpressFareEntry {
+        line,
+        station_pair,
+        reserved_hikari_fare: Amount { value: reserved_hikari_fare },
+        nozomi_addition: Amount { value: nozomi_addition },
+    };
+    vec![
+        entry(ShinkansenLine::Tokaido, (Station::Tokyo, Station::ShinOsaka), 5490, 320),
+        entry(ShinkansenLine::Sanyo, (Station::ShinOsaka, Station::Himeji), 3270, 210),
+        entry(ShinkansenLine::Sanyo, (Station::ShinOsaka, Station::Hakata), 5600, 420),
+        entry(ShinkansenLine::Sanyo, (Station::Himeji, Station::Hakata), 5380, 420),
+        entry(ShinkansenLine::Kyushu, (Station::Hakata, Station::KagoshimaChuo), 5230, 210),
+    ]
+}
+
+// 東海道新幹線と山陽新幹線を直通する場合のひかり指定席特急料金 ( 通常期 )
+// 路線ごとの料金を合算せず、通算した営業キロの帯 ( 下限を含まず上限を含む ) で定める
+const TOKAIDO_SANYO_EXPRESS_FARE_BANDS: [(f64, f64, u64); 6] = [
+    (553.0, 700.0, 5920),
+    (700.0, 800.0, 6470),
+    (800.0, 900.0, 7040),
+    (900.0, 1000.0, 7470),
+    (1000.0, 1100.0, 8080),
+    (1100.0, 1200.0, 8570),
+];
+
+// 山陽新幹線と九州新幹線を直通する列車が運転される区間の、通しのさくら指定席特急料金 ( 通常期 )
+pub struct ThroughExpressFareEntry {
+    pub station_pair: (Station, Station),
+    pub reserved_hikari_fare: Amount,
+}
+
+pub fn get_through_express_fare_table() -> Vec<ThroughExpressFareEntry> {
+    vec![
+        ThroughExpressFareEntry {
+            station_pair: (Station::ShinOsaka, Station::KagoshimaChuo),
+            reserved_hikari_fare: Amount { value: 8220 },
+        },
+        ThroughExpressFareEntry {
+            station_pair: (Station::Himeji, Station::KagoshimaChuo),
+            reserved_hikari_fare: Amount { value: 7790 },
+        },
+    ]
+}
+
+fn find_line_express_fare_entry(line_section: &LineSection) -> LineExpressFareEntry {
+    let (departure, arrival) = line_section.ride_section.get_station_pair();
+    get_line_express_fare_table()
+        .into_iter()
+        .find(|entry| entry.line == line_section.line && entry.station_pair == (*departure, *arrival))
+        .unwrap_or_else(|| panic!("unexpected ride section"))
+}
+
+fn find_through_express_fare_entry(ride_section: &RideSection) -> Option<ThroughExpressFareEntry> {
+    let (departure, arrival) = ride_section.get_station_pair();
+    get_through_express_fare_table().into_iter().find(|entry| entry.station_pair == (*departure, *arrival))
+}
+
+// 特急料金を算出する単位に乗車区間を分け、乗車する順に返す
+// JR 西日本と JR 九州の境界 ( 博多 ) をまたぎ、通しの料金がない場合は博多で分けて料金を合算する
+pub fn split_into_fare_sections(ride_section: &RideSection) -> Vec<RideSection> {
+    let ride_section = RideSection { departure_connection: None, arrival_connection: None, ..*ride_section };
+    let line_sections = split_by_line(&ride_section);
+    let kyushu_index = line_sections.iter().position(|line_section| line_section.line == ShinkansenLine::Kyushu);
+    match kyushu_index {
+        Some(index) if 1 < line_sections.len() && find_through_express_fare_entry(&ride_section).is_none() => {
+            let kyushu = line_sections[index].ride_section;
+            if index == 0 {
+                vec![kyushu, RideSection { departure: kyushu.arrival, ..ride_section }]
+            } else {
+                vec![RideSection { arrival: kyushu.departure, ..ride_section }, kyushu]
+            }
+        }
+        _ => vec![ride_section],
+    }
+}
+
 pub fn calc_express_fare(
     train: &Train,
     seat_type: &SeatType,
     ride_section: &RideSection,
     departure_date: &DepartureDate,
+) -> anyhow::Result<ExpressFare> {
+    let value = calc_express_fare_by_operator(train, seat_type, ride_section, departure_date)?
+        .into_iter()
+        .fold(Amount { value: 0 }, |sum, (_, express_fare)| sum + express_fare.value);
+    Ok(ExpressFare { value })
+}
+
+// 特急料金を会社ごとに分ける
+// 料金を合算する場合は区間ごとの料金を、複数の会社にまたがる区間の料金は営業キロの比で按分した額を、その会社の収入とする
+pub fn calc_express_fare_by_operator(
+    train: &Train,
+    seat_type: &SeatType,
+    ride_section: &RideSection,
+    departure_date: &DepartureDate,
+) -> anyhow::Result<Vec<(Operator, ExpressFare)>> {
+    let mut express_fares: Vec<(Operator, ExpressFare)> = vec![];
+    for fare_section in split_into_fare_sections(ride_section) {
+        let express_fare = calc_section_express_fare(train, seat_type, &fare_section, departure_date);
+        let line_sections = split_by_line(&fare_section);
+        let kilometers = line_sections
+            .iter()
+            .map(|line_section| line_section.ride_section.get_operation_kilometer().to_tenths())
+            .collect::<Vec<_>>();
+        for (line_section, value) in line_sections.iter().zip(express_fare.value.apportion(&kilometers)?) {
+            let operator = line_section.line.get_operator();
+            match express_fares.iter_mut().find(|(other, _)| *other == operator) {
+                Some((_, express_fare)) => express_fare.value = express_fare.value.clone() + value,
+                None => express_fares.push((operator, ExpressFare { value })),
+            }
+        }
+    }
+    Ok(express_fares)
+}
+
+fn calc_section_express_fare(
+    train: &Train,
+    seat_type: &SeatType,
+    ride_section: &RideSection,
+    departure_date: &DepartureDate,
 ) -> ExpressFare {
     fn create_reserved_hikari_express_fare(ride_section: &RideSection) -> Amount {
-        match ride_section.get_station_pair() {
-            (Station::Tokyo, Station::ShinOsaka) => Amount { value: 5490 },
-            (Station::Tokyo, Station::Himeji) => Amount { value: 5920 },
-            _ => panic!("unexpected ride section"),
+        if let Some(entry) = find_through_express_fare_entry(ride_section) {
+            return entry.reserved_hikari_fare;
+        }
+        match split_by_line(ride_section).as_slice() {
+            [line_section] => find_line_express_fare_entry(line_section).reserved_hikari_fare,
+            _ => {
+                let kilometer = ride_section.get_operation_kilometer().value;
+                let (_, _, fare) = TOKAIDO_SANYO_EXPRESS_FARE_BANDS
+                    .iter()
+                    .find(|(lower, upper, _)| *lower < kilometer && kilometer <= *upper)
+                    .unwrap_or_else(|| panic!("unexpected ride section"));
+                Amount { value: *fare }
+            }
         }
     }
 
+    // のぞみ ( みずほ ) の加算額は直通する場合も路線ごとの加算額を合算する
     fn create_reserved_nozomi_express_fare(ride_section: &RideSection) -> Amount {
         let hikari = create_reserved_hikari_express_fare(ride_section);
-        let addition = match ride_section.get_station_pair() {
-            (Station::Tokyo, Station::ShinOsaka) => Amount { value: 320 },
-            (Station::Tokyo, Station::Himeji) => Amount { value: 530 },
-            _ => panic!("unexpected ride section"),
-        };
-        hikari + addition
+        split_by_line(ride_section)
+            .iter()
+            .map(|line_section| find_line_express_fare_entry(line_section).nozomi_addition)
+            .fold(hikari, |sum, addition| sum + addition)
     }
 
     fn create_free_express_fare(ride_section: &RideSection) -> Amount {
@@ -40,19 +216,12 @@ pub fn calc_express_fare(
     }
 
     let amount = match (seat_type, train) {
-        (SeatType::Reserved, Train::Hikari) => create_reserved_hikari_express_fare(ride_section),
-        (SeatType::Reserved, Train::Nozomi) => create_reserved_nozomi_express_fare(ride_section),
+        (SeatType::Reserved, Train::Hikari | Train::Sakura) => create_reserved_hikari_express_fare(ride_section),
+        (SeatType::Reserved, Train::Nozomi | Train::Mizuho) => create_reserved_nozomi_express_fare(ride_section),
         (SeatType::Free, _) => create_free_express_fare(ride_section),
     };
 
-    ExpressFare {
-        value: match (seat_type, departure_date.get_season()) {
-            (SeatType::Reserved, Season::Regular) => amount,
-            (SeatType::Reserved, Season::OffPeak) => amount - Amount { value: 200 },
-            (SeatType::Reserved, Season::Peak) => amount + Amount { value: 200 },
-            (SeatType::Free, _) => amount,
-        },
-    }
+    ExpressFare { value: judge_season_adjustment(seat_type, departure_date).apply(amount) }
 }
 
 #[cfg(test)]
@@ -61,13 +230,17 @@ mod tests {
     use rstest::rstest;
 
     use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::operator::Operator;
+    use crate::domain::base::operator::Operator::*;
     use crate::domain::base::ride_section::Station::*;
     use crate::domain::base::ride_section::{RideSection, Station};
     use crate::domain::base::seat_type::SeatType;
     use crate::domain::base::seat_type::SeatType::*;
     use crate::domain::base::train::Train;
     use crate::domain::base::train::Train::*;
-    use crate::domain::fare::express_fare::{calc_express_fare, ExpressFare};
+    use crate::domain::fare::express_fare::{
+        calc_express_fare, calc_express_fare_by_operator, split_into_fare_sections, ExpressFare,
+    };
     use crate::fundamental::amount::Amount;
 
     #[rstest]
@@ -127,6 +300,28 @@ mod tests {
     #[case(Nozomi, Free, Himeji, Tokyo, 1, 20, 5390)]
     #[case(Nozomi, Free, Tokyo, Himeji, 1, 10, 5390)]
     #[case(Nozomi, Free, Himeji, Tokyo, 1, 10, 5390)]
+    // 山陽新幹線だけを乗車する
+    #[case(Hikari, Reserved, ShinOsaka, Himeji, 1, 15, 3270)]
+    #[case(Nozomi, Reserved, Himeji, ShinOsaka, 1, 15, 3480)]
+    #[case(Nozomi, Reserved, Hakata, ShinOsaka, 1, 10, 6220)]
+    #[case(Nozomi, Free, Himeji, Hakata, 1, 15, 4850)]
+    // 直通する場合は通算した営業キロの料金に路線ごとののぞみの加算額を合算する
+    #[case(Hikari, Reserved, Tokyo, Hakata, 1, 15, 8570)]
+    #[case(Nozomi, Reserved, Hakata, Tokyo, 1, 15, 9310)]
+    #[case(Nozomi, Reserved, Tokyo, Hakata, 1, 20, 9110)]
+    #[case(Nozomi, Free, Tokyo, Hakata, 1, 15, 8040)]
+    // 九州新幹線だけを乗車する
+    #[case(Sakura, Reserved, Hakata, KagoshimaChuo, 1, 15, 5230)]
+    #[case(Mizuho, Reserved, KagoshimaChuo, Hakata, 1, 15, 5440)]
+    // 山陽新幹線と九州新幹線を直通する区間は通しの料金に路線ごとのみずほの加算額を合算する
+    #[case(Sakura, Reserved, Himeji, KagoshimaChuo, 1, 15, 7790)]
+    #[case(Mizuho, Reserved, KagoshimaChuo, ShinOsaka, 1, 15, 8850)]
+    #[case(Mizuho, Free, ShinOsaka, KagoshimaChuo, 1, 10, 7690)]
+    // 通しの料金がない区間は博多で分けた料金を合算する ( 季節による増減も区間ごと )
+    #[case(Hikari, Reserved, Tokyo, KagoshimaChuo, 1, 15, 13800)]
+    #[case(Nozomi, Reserved, KagoshimaChuo, Tokyo, 1, 15, 14750)]
+    #[case(Nozomi, Reserved, Tokyo, KagoshimaChuo, 1, 10, 15150)]
+    #[case(Nozomi, Free, Tokyo, KagoshimaChuo, 1, 10, 12740)]
     fn test_create_express_fare(
         #[case] train: Train,
         #[case] seat_type: SeatType,
@@ -136,11 +331,53 @@ mod tests {
         #[case] d: u32,
         #[case] exp: u64,
     ) {
-        let ride_section = RideSection { departure, arrival };
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
         let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
         assert_eq!(
             ExpressFare { value: Amount { value: exp } },
-            calc_express_fare(&train, &seat_type, &ride_section, &departure_date)
+            calc_express_fare(&train, &seat_type, &ride_section, &departure_date).unwrap()
         );
     }
+
+    #[rstest]
+    #[case(Tokyo, Himeji, vec![(Tokyo, Himeji)])]
+    #[case(ShinOsaka, KagoshimaChuo, vec![(ShinOsaka, KagoshimaChuo)])]
+    #[case(Tokyo, KagoshimaChuo, vec![(Tokyo, Hakata), (Hakata, KagoshimaChuo)])]
+    #[case(KagoshimaChuo, Tokyo, vec![(KagoshimaChuo, Hakata), (Hakata, Tokyo)])]
+    fn fare_sections(#[case] departure: Station, #[case] arrival: Station, #[case] exp: Vec<(Station, Station)>) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        let exp = exp
+            .into_iter()
+            .map(|(departure, arrival)| RideSection {
+                departure,
+                arrival,
+                departure_connection: None,
+                arrival_connection: None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(exp, split_into_fare_sections(&ride_section));
+    }
+
+    #[rstest]
+    #[case(Nozomi, ShinOsaka, Tokyo, vec![(Central, 5810)])]
+    // 複数の会社にまたがる区間の料金は営業キロの比で按分する
+    #[case(Nozomi, Tokyo, Himeji, vec![(Central, 5539), (West, 911)])]
+    #[case(Mizuho, ShinOsaka, KagoshimaChuo, vec![(West, 6043), (Kyushu, 2807)])]
+    // 料金を合算する場合は区間ごとの料金をその会社の収入とする
+    #[case(Nozomi, Tokyo, KagoshimaChuo, vec![(Central, 4382), (West, 4928), (Kyushu, 5440)])]
+    #[case(Nozomi, KagoshimaChuo, Tokyo, vec![(Kyushu, 5440), (West, 4928), (Central, 4382)])]
+    fn by_operator(
+        #[case] train: Train,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] exp: Vec<(Operator, u64)>,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap() };
+        let exp = exp
+            .into_iter()
+            .map(|(operator, value)| (operator, ExpressFare { value: Amount { value } }))
+            .collect::<Vec<_>>();
+        assert_eq!(exp, calc_express_fare_by_operator(&train, &Reserved, &ride_section, &departure_date).unwrap());
+    }
 }
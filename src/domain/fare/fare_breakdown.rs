@@ -0,0 +1,141 @@
+use crate::domain::base::operator::Operator;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::discount::individual_discount::IndividualDiscount;
+use crate::domain::discount::overall_discount::OverallDiscount;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::operator_revenue::{attribute_revenue, OperatorRevenue};
+use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+use crate::domain::fare::special_section::SpecialFareApplication;
+use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
+use crate::domain::ticket::validity_period::{calc_validity_period, ValidityPeriod};
+use crate::fundamental::amount::Amount;
+
+// 総料金の内訳
+// notes には計算の過程で下した判断を記録する
+#[derive(PartialEq, Debug)]
+pub struct FareBreakdown {
+    pub train_fare: TrainFare,
+    pub train_fare_type: TrainFareType,
+    pub express_fare: ExpressFare,
+    pub special_fare: SpecialFareApplication,
+    // 1 方向ごとに発券する乗車券の有効期間と途中下車の可否
+    pub validity_period: ValidityPeriod,
+    pub operator_revenues: Vec<OperatorRevenue>,
+    pub discounts: Vec<String>,
+    pub notes: Vec<String>,
+    pub total_fare: Amount,
+}
+
+pub fn create_fare_breakdown(
+    single_trip_adult_fare: &SingleTripAdultFare,
+    special_fare: SpecialFareApplication,
+    ride_section: &RideSection,
+    regular_express_fares: &[(Operator, ExpressFare)],
+    overall_discount: &Option<OverallDiscount>,
+    individual_discounts: &[IndividualDiscount],
+    total_fare: Amount,
+) -> anyhow::Result<FareBreakdown> {
+    let (train_fare, express_fare) = single_trip_adult_fare.get_adult_fare();
+    let operator_revenues =
+        attribute_revenue(&train_fare, &express_fare, &total_fare, ride_section, regular_express_fares)?;
+
+    let mut discounts = individual_discounts.iter().map(IndividualDiscount::describe).collect::<Vec<_>>();
+    let mut notes = vec![];
+    if let Some(overall_discount) = overall_discount {
+        discounts.push(overall_discount.describe());
+        notes.push(overall_discount.explain());
+    }
+
+    let train_fare_type = single_trip_adult_fare.train_fare_type;
+    let validity_period = calc_validity_period(&ride_section.get_fare_kilometer());
+    Ok(FareBreakdown {
+        train_fare,
+        train_fare_type,
+        express_fare,
+        special_fare,
+        validity_period,
+        operator_revenues,
+        discounts,
+        notes,
+        total_fare,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::base::number_of_passengers::NumberOfPassengers;
+    use crate::domain::base::operator::Operator::*;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::discount::overall_discount::OverallDiscount::LargeGroupDiscount;
+    use crate::domain::discount::round_trip_discount::create_round_trip_discount;
+    use crate::domain::fare::express_fare::{ExpressFare, SeasonAdjustment};
+    use crate::domain::fare::fare_breakdown::{create_fare_breakdown, FareBreakdown};
+    use crate::domain::fare::operator_revenue::OperatorRevenue;
+    use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+    use crate::domain::fare::special_section::SpecialFareApplication;
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::domain::fare::train_fare::TrainFareType::Ticket;
+    use crate::domain::ticket::validity_period::ValidityPeriod;
+    use crate::fundamental::amount::Amount;
+
+    #[test]
+    fn test_create_fare_breakdown() {
+        let single_trip_adult_fare = SingleTripAdultFare {
+            train_fare: TrainFare { value: Amount { value: 10010 } },
+            train_fare_type: Ticket,
+            express_fare: ExpressFare { value: Amount { value: 6650 } },
+            season_adjustment: SeasonAdjustment::Unadjusted,
+        };
+        assert_eq!(
+            FareBreakdown {
+                train_fare: TrainFare { value: Amount { value: 10010 } },
+                train_fare_type: Ticket,
+                express_fare: ExpressFare { value: Amount { value: 6650 } },
+                special_fare: SpecialFareApplication::default(),
+                validity_period: ValidityPeriod { days: 5, stopover: true },
+                operator_revenues: vec![
+                    OperatorRevenue {
+                        operator: Central,
+                        train_fare: TrainFare { value: Amount { value: 8596 } },
+                        express_fare: ExpressFare { value: Amount { value: 5711 } },
+                        total_fare: Amount { value: 1316914 },
+                    },
+                    OperatorRevenue {
+                        operator: West,
+                        train_fare: TrainFare { value: Amount { value: 1414 } },
+                        express_fare: ExpressFare { value: Amount { value: 939 } },
+                        total_fare: Amount { value: 216586 },
+                    },
+                ],
+                discounts: vec![
+                    "往復割引 ( 運賃 x 0.9 )".to_string(),
+                    "団体割引 ( 31 名以上 ) 無料 おとな 1 名".to_string()
+                ],
+                notes: vec!["団体割引で無料になる旅客は料金の高いおとなから選びます".to_string()],
+                total_fare: Amount { value: 1533500 },
+            },
+            create_fare_breakdown(
+                &single_trip_adult_fare,
+                SpecialFareApplication::default(),
+                &RideSection {
+                    departure: Tokyo,
+                    arrival: Himeji,
+                    departure_connection: None,
+                    arrival_connection: None
+                },
+                &[
+                    (Central, ExpressFare { value: Amount { value: 5711 } }),
+                    (West, ExpressFare { value: Amount { value: 939 } }),
+                ],
+                &Some(LargeGroupDiscount {
+                    min_passengers: 31,
+                    free_passengers: NumberOfPassengers { adult: 1, child: 0 }
+                }),
+                &[create_round_trip_discount()],
+                Amount { value: 1533500 }
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::domain::base::departure_date::Season;
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::ride_section::Station;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::fare::express_fare::{ExpressFare, SeasonAdjustment};
+use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+use crate::domain::fare::train_fare::{calc_train_fare, TrainFare, TrainFareType};
+use crate::fundamental::amount::Amount;
+
+// 運賃と特急料金の組み合わせを置き換える、予約日によって決まるパッケージ商品
+// 価格は片道おとな 1 人あたりで、個別割引とは併用できない
+// 商品ごとのこども価格は扱わず、こどもは他の料金と同じくおとなの価格を折半する ( 10 円未満切り捨て )
+#[derive(PartialEq, Debug)]
+pub struct FareProduct {
+    pub name: &'static str,
+    pub min_lead_days: i64,
+    // 発売するシーズン ( 年末年始などの繁忙期は発売しない商品がある )
+    pub seasons: Vec<Season>,
+    pub station_pair: (Station, Station),
+    pub train: Train,
+    pub seat_type: SeatType,
+    pub package_price: Amount,
+}
+
+pub fn get_fare_products() -> Vec<FareProduct> {
+    vec![FareProduct {
+        name: "EX早特21ワイド",
+        min_lead_days: 21,
+        seasons: vec![Season::Regular, Season::OffPeak],
+        station_pair: (Station::Tokyo, Station::ShinOsaka),
+        train: Train::Nozomi,
+        seat_type: SeatType::Reserved,
+        package_price: Amount { value: 12370 },
+    }]
+}
+
+// 適用できる商品のうち最も安いものを選ぶ
+// 商品は新幹線の駅間に限るため、在来線で乗り継ぐ場合は適用しない
+pub fn judge_fare_product<'a>(fare_query: &FareQuery, fare_products: &'a [FareProduct]) -> Option<&'a FareProduct> {
+    if fare_query.ride_section.has_connections() {
+        return None;
+    }
+    let (departure, arrival) = fare_query.ride_section.get_station_pair();
+    let lead_days = fare_query.booking_date.get_lead_days(&fare_query.departure_date);
+
+    fare_products
+        .iter()
+        .filter(|product| product.station_pair == (*departure, *arrival))
+        .filter(|product| product.train == fare_query.train && product.seat_type == fare_query.seat_type)
+        .filter(|product| product.min_lead_days <= lead_days)
+        .filter(|product| product.seasons.contains(fare_query.departure_date.get_season()))
+        .min_by_key(|product| product.package_price.value)
+}
+
+// 券面に運賃と特急料金を分けて記載するため、通常の運賃を上限に運賃を割り当てて残りを特急料金とする
+pub fn calc_fare_product_fare(
+    fare_product: &FareProduct,
+    fare_query: &FareQuery,
+) -> anyhow::Result<SingleTripAdultFare> {
+    let train_fare = calc_train_fare(&fare_query.ride_section)?.value;
+    let package_price = fare_product.package_price.clone();
+
+    let train_fare = if train_fare.value < package_price.value { train_fare } else { package_price.clone() };
+    let express_fare = package_price - train_fare.clone();
+
+    Ok(SingleTripAdultFare {
+        train_fare: TrainFare { value: train_fare },
+        train_fare_type: TrainFareType::Ticket,
+        express_fare: ExpressFare { value: express_fare },
+        season_adjustment: SeasonAdjustment::Unadjusted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::booking_date::BookingDate;
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::departure_date::{DepartureDate, Season};
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::fare_product::{
+        calc_fare_product_fare, get_fare_products, judge_fare_product, FareProduct,
+    };
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::fundamental::amount::Amount;
+
+    fn fare_query(arrival: Station, train: Train, seat_type: SeatType, booking_m: u32, booking_d: u32) -> FareQuery {
+        FareQuery {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train,
+            seat_type,
+            booking_date: BookingDate { value: NaiveDate::from_ymd_opt(2024, booking_m, booking_d).unwrap() },
+            ..FareQuery::default()
+        }
+    }
+
+    #[rstest]
+    #[case(ShinOsaka, Nozomi, Reserved, 2, 1, Some("EX早特21ワイド"))]
+    #[case(ShinOsaka, Nozomi, Reserved, 1, 1, Some("EX早特21ワイド"))]
+    #[case(ShinOsaka, Nozomi, Reserved, 2, 2, None)]
+    #[case(ShinOsaka, Hikari, Reserved, 2, 1, None)]
+    #[case(ShinOsaka, Nozomi, Free, 2, 1, None)]
+    #[case(Himeji, Nozomi, Reserved, 2, 1, None)]
+    fn test_judge_fare_product(
+        #[case] arrival: Station,
+        #[case] train: Train,
+        #[case] seat_type: SeatType,
+        #[case] booking_m: u32,
+        #[case] booking_d: u32,
+        #[case] exp: Option<&str>,
+    ) {
+        let fare_query = fare_query(arrival, train, seat_type, booking_m, booking_d);
+        let fare_products = get_fare_products();
+        assert_eq!(exp, judge_fare_product(&fare_query, &fare_products).map(|product| product.name));
+    }
+
+    // 繁忙期は発売しない
+    #[test]
+    fn peak_season() {
+        let fare_query = FareQuery {
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() },
+            booking_date: BookingDate { value: NaiveDate::from_ymd_opt(2024, 11, 1).unwrap() },
+            ..fare_query(ShinOsaka, Nozomi, Reserved, 2, 1)
+        };
+        assert_eq!(None, judge_fare_product(&fare_query, &get_fare_products()));
+    }
+
+    // 在来線で乗り継ぐ場合は商品を適用しない
+    #[test]
+    fn with_connection() {
+        let mut fare_query = fare_query(ShinOsaka, Nozomi, Reserved, 2, 1);
+        fare_query.ride_section.departure_connection = Some(ConventionalStation::Shinagawa);
+        assert_eq!(None, judge_fare_product(&fare_query, &get_fare_products()));
+    }
+
+    #[rstest]
+    #[case(12370, 8910, 3460)]
+    #[case(8000, 8000, 0)]
+    fn test_calc_fare_product_fare(#[case] package_price: u64, #[case] train_fare: u64, #[case] express_fare: u64) {
+        let fare_product = FareProduct {
+            name: "テスト",
+            min_lead_days: 0,
+            seasons: vec![Season::Regular],
+            station_pair: (Tokyo, ShinOsaka),
+            train: Nozomi,
+            seat_type: Reserved,
+            package_price: Amount { value: package_price },
+        };
+        let act = calc_fare_product_fare(&fare_product, &fare_query(ShinOsaka, Nozomi, Reserved, 2, 1)).unwrap();
+        assert_eq!(TrainFare { value: Amount { value: train_fare } }, act.train_fare);
+        assert_eq!(ExpressFare { value: Amount { value: express_fare } }, act.express_fare);
+    }
+}
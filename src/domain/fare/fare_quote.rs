@@ -0,0 +1,380 @@
+use std::rc::Rc;
+
+use anyhow::{anyhow, bail};
+
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::operator::Operator;
+use crate::domain::base::payment_medium::PaymentMedium;
+use crate::domain::base::stop_pattern::{describe_partly_served, get_stop_patterns, judge_stop_pattern};
+use crate::domain::discount::discount_policy::DiscountPolicy;
+use crate::domain::discount::discount_rule::DiscountRuleRegistry;
+use crate::domain::discount::individual_discount::{judge_individual_discounts, IndividualDiscount};
+use crate::domain::discount::overall_discount::{judge_overall_discount, OverallDiscount};
+use crate::domain::discount::promotion::{judge_promotion, Promotion, PromotionRejection};
+use crate::domain::fare::ex_express_fare::{calc_ex_express_fare, judge_ex_coverage};
+use crate::domain::fare::express_fare::{
+    calc_express_fare, calc_express_fare_by_operator, judge_season_adjustment, ExpressFare, SeasonAdjustment,
+};
+use crate::domain::fare::fare_product::{calc_fare_product_fare, get_fare_products, judge_fare_product};
+use crate::domain::fare::seat_availability::judge_seat_availability;
+use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+use crate::domain::fare::special_section::{
+    get_special_sections, judge_special_express_fare, judge_special_train_fare, SpecialFareApplication,
+};
+use crate::domain::fare::train_fare::{calc_train_fare_by_medium, get_train_fare_table, TrainFareType};
+
+// 総料金の算出と発券に共通する、割引適用前の片道おとな料金と適用される割引
+pub struct FareQuote {
+    pub single_trip_adult_fare: SingleTripAdultFare,
+    pub regular_express_fares: Vec<(Operator, ExpressFare)>,
+    pub overall_discount: Option<OverallDiscount>,
+    pub individual_discounts: Vec<IndividualDiscount>,
+    pub applied_promotion_code: Option<String>,
+    pub special_fare: SpecialFareApplication,
+    pub notes: Vec<String>,
+}
+
+pub fn calc_fare_quote(
+    fare_query: &FareQuery,
+    discount_policy: &DiscountPolicy,
+    promotion_code: &Option<String>,
+    promotions: &[Promotion],
+) -> anyhow::Result<FareQuote> {
+    // 乗車日に利用できない列車と座席の組み合わせは料金を算出しない
+    judge_seat_availability(&fare_query.train, &fare_query.seat_type, &fare_query.departure_date)
+        .map_err(|unavailability| anyhow!(unavailability.describe()))?;
+
+    // 在来線の駅は乗り継ぐ新幹線の駅と結ばれている必要がある
+    if !fare_query.ride_section.has_valid_connections() {
+        bail!("在来線の駅が乗り継ぐ新幹線の駅と結ばれていません");
+    }
+
+    // EX サービスの商品は新幹線の駅の間に限る ( 特定特急料金を適用する場合も同じ )
+    if let Some(ex_product) = &fare_query.ex_product {
+        judge_ex_coverage(ex_product, &fare_query.ride_section)?;
+    }
+
+    let mut notes = vec![];
+
+    // 発駅と着駅に停車しない列車は料金を算出せず、一部の列車だけが停車する駅は警告する
+    let partly_served = judge_stop_pattern(&fare_query.train, &fare_query.ride_section, &get_stop_patterns())
+        .map_err(|violation| anyhow!(violation.describe()))?;
+    for station in &partly_served {
+        notes.push(describe_partly_served(&fare_query.train, station));
+    }
+
+    // 予約日によって適用できる商品があれば、運賃と特急料金の代わりにその価格を用いる
+    let fare_products = get_fare_products();
+    let fare_product = judge_fare_product(fare_query, &fare_products);
+
+    // 片道おとな料金を算出する
+    // 運賃は支払い方法によって異なる場合がある
+    let mut special_fare = SpecialFareApplication::default();
+    let single_trip_adult_fare = match fare_product {
+        Some(fare_product) => {
+            notes.push(format!("{} を適用したため個別割引は適用しません", fare_product.name));
+            calc_fare_product_fare(fare_product, fare_query)?
+        }
+        None => {
+            // 特定区間の運賃と特急料金は通常の計算より優先する
+            let special_sections = get_special_sections();
+            let special_express_fare =
+                judge_special_express_fare(&fare_query.ride_section, &fare_query.seat_type, &special_sections);
+
+            // EX サービスで予約する場合の運賃は支払い方法によらずきっぷと同じ
+            let payment_medium = match fare_query.ex_product {
+                Some(_) => PaymentMedium::Ticket,
+                None => fare_query.payment_medium,
+            };
+            let (train_fare, train_fare_type) =
+                calc_train_fare_by_medium(&fare_query.ride_section, &payment_medium, &get_train_fare_table())?;
+            if payment_medium == PaymentMedium::IcCard && train_fare_type == TrainFareType::Ticket {
+                notes.push("IC 運賃の設定がない区間のため、きっぷの運賃を適用します".to_string());
+            }
+            // 特定区間運賃は、通常の運賃と同じ種類 ( IC の定めがなければきっぷ ) の運賃に置き換える
+            let special_train_fare =
+                judge_special_train_fare(&fare_query.ride_section, &train_fare_type, &special_sections);
+            special_fare = SpecialFareApplication {
+                train_fare: special_train_fare.is_some(),
+                express_fare: special_express_fare.is_some(),
+            };
+            let (train_fare, train_fare_type) = special_train_fare.unwrap_or((train_fare, train_fare_type));
+
+            // 特定特急料金がなければ、EX サービスの商品は特急料金を商品ごとの料金に置き換える
+            // 特定特急料金と EX 予約の特急料金はシーズンによらない
+            let season_adjustment = judge_season_adjustment(&fare_query.seat_type, &fare_query.departure_date);
+            let (express_fare, season_adjustment) = match (fare_query.ex_product, special_express_fare) {
+                (_, Some(express_fare)) => (express_fare, SeasonAdjustment::Unadjusted),
+                (Some(ex_product), None) => {
+                    notes.push(format!("{} の特急料金を適用します", ex_product.describe()));
+                    let express_fare = calc_ex_express_fare(
+                        &ex_product,
+                        &fare_query.train,
+                        &fare_query.seat_type,
+                        &fare_query.ride_section,
+                        &fare_query.departure_date,
+                    )?;
+                    match ex_product {
+                        ExProduct::SmartEx => (express_fare, season_adjustment),
+                        ExProduct::ExReservation => (express_fare, SeasonAdjustment::Unadjusted),
+                    }
+                }
+                (None, None) => (
+                    calc_express_fare(
+                        &fare_query.train,
+                        &fare_query.seat_type,
+                        &fare_query.ride_section,
+                        &fare_query.departure_date,
+                    )?,
+                    season_adjustment,
+                ),
+            };
+
+            SingleTripAdultFare { train_fare, train_fare_type, express_fare, season_adjustment }
+        }
+    };
+
+    // 適用される全体割引を判定する
+    let overall_discount = judge_overall_discount(
+        &fare_query.group_type,
+        &fare_query.number_of_passengers,
+        &discount_policy.group_free_passenger,
+    );
+
+    // 適用される個別割引とプロモーションを判定する
+    // プロモーションは個別割引と同じく割引規則として登録する
+    let mut applied_promotion_code = None;
+    let individual_discounts = match fare_product {
+        Some(_) => vec![],
+        None => {
+            let mut discount_rule_registry = DiscountRuleRegistry::default();
+            if let Some(code) = promotion_code {
+                match judge_promotion(code, promotions, fare_query) {
+                    Ok(promotion_rules) => {
+                        for promotion_rule in promotion_rules {
+                            discount_rule_registry.register(Rc::new(promotion_rule));
+                        }
+                        applied_promotion_code = Some(code.clone());
+                    }
+                    Err(rejection) => {
+                        notes.push(format!("プロモーションコード {code} は適用できません ( {} )", rejection.describe()))
+                    }
+                }
+            }
+            judge_individual_discounts(fare_query, &discount_rule_registry)
+        }
+    };
+
+    // 会社ごとの収入を按分するため、通常の特急料金の会社ごとの内訳を求める
+    let regular_express_fares = calc_express_fare_by_operator(
+        &fare_query.train,
+        &fare_query.seat_type,
+        &fare_query.ride_section,
+        &fare_query.departure_date,
+    )?;
+
+    Ok(FareQuote {
+        single_trip_adult_fare,
+        regular_express_fares,
+        overall_discount,
+        individual_discounts,
+        applied_promotion_code,
+        special_fare,
+        notes,
+    })
+}
+
+// 適用したプロモーションの利用数を乗車人数分消費する
+pub fn consume_promotion(
+    promotions: &mut [Promotion],
+    fare_quote: &FareQuote,
+    fare_query: &FareQuery,
+) -> anyhow::Result<()> {
+    let Some(code) = &fare_quote.applied_promotion_code else {
+        return Ok(());
+    };
+    let promotion = promotions
+        .iter_mut()
+        .find(|promotion| promotion.code == *code)
+        .ok_or_else(|| anyhow!(PromotionRejection::UnknownCode.describe()))?;
+    promotion.consume(&fare_query.number_of_passengers).map_err(|rejection| anyhow!(rejection.describe()))
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::booking_date::BookingDate;
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::ex_product::ExProduct;
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::number_of_passengers::NumberOfPassengers;
+    use crate::domain::base::payment_medium::PaymentMedium;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::train::Train::Hikari;
+    use crate::domain::discount::discount_policy::{
+        DiscountMethod, DiscountPolicy, DiscountTarget, RoundingPoint, StackingMode,
+    };
+    use crate::domain::discount::promotion::Promotion;
+    use crate::domain::fare::fare_quote::{calc_fare_quote, consume_promotion};
+    use crate::domain::fare::special_section::SpecialFareApplication;
+    use crate::domain::fare::train_fare::TrainFareType;
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::rounding::Rounding;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+
+    // 2 月 22 日は通常期
+    fn fare_query(booking_date: NaiveDate) -> FareQuery {
+        FareQuery {
+            number_of_passengers: NumberOfPassengers { adult: 2, child: 0 },
+            booking_date: BookingDate { value: booking_date },
+            ..FareQuery::default()
+        }
+    }
+
+    fn promotions() -> Vec<Promotion> {
+        vec![Promotion {
+            code: "EXHAYA".to_string(),
+            name: "EX早特".to_string(),
+            booking_period: date(2, 1)..=date(2, 29),
+            travel_period: date(2, 1)..=date(2, 29),
+            station_pairs: vec![],
+            trains: vec![],
+            seat_types: vec![],
+            target: DiscountTarget::ExpressFare,
+            discount_method: DiscountMethod::FixedAmount(Amount { value: 1000 }),
+            rounding: Rounding::FLOOR_TO_TEN,
+            stacking_mode: StackingMode::Sequential,
+            rounding_point: RoundingPoint::EachStep,
+            max_uses: 2,
+            used_count: 0,
+        }]
+    }
+
+    #[rstest]
+    // 21 日前までに予約すると商品の価格を用い、プロモーションを含む個別割引は適用しない
+    #[case(date(1, 31), Some("EXHAYA"), 8910, 3460, vec![], vec!["EX早特21ワイド を適用したため個別割引は適用しません"])]
+    // 商品を適用できなければプロモーションを個別割引として適用する
+    #[case(date(2, 10), Some("EXHAYA"), 8910, 5810, vec!["EX早特"], vec![])]
+    #[case(date(2, 10), None, 8910, 5810, vec![], vec![])]
+    #[case(
+        date(2, 10),
+        Some("UNKNOWN"),
+        8910,
+        5810,
+        vec![],
+        vec!["プロモーションコード UNKNOWN は適用できません ( 存在しないコードです )"]
+    )]
+    fn quote(
+        #[case] booking_date: NaiveDate,
+        #[case] promotion_code: Option<&str>,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+        #[case] discounts: Vec<&str>,
+        #[case] notes: Vec<&str>,
+    ) {
+        let fare_query = fare_query(booking_date);
+        let act = calc_fare_quote(
+            &fare_query,
+            &DiscountPolicy::default(),
+            &promotion_code.map(str::to_string),
+            &promotions(),
+        )
+        .unwrap();
+        assert_eq!(train_fare, act.single_trip_adult_fare.train_fare.value.value);
+        assert_eq!(express_fare, act.single_trip_adult_fare.express_fare.value.value);
+        assert_eq!(
+            discounts,
+            act.individual_discounts.iter().map(|discount| discount.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(notes, act.notes);
+    }
+
+    // EX サービスで予約する場合は IC カードで支払ってもきっぷの運賃を適用する
+    #[test]
+    fn ex_product_with_ic_card() {
+        let fare_query = FareQuery {
+            payment_medium: PaymentMedium::IcCard,
+            ex_product: Some(ExProduct::SmartEx),
+            ..fare_query(date(2, 15))
+        };
+        let act = calc_fare_quote(&fare_query, &DiscountPolicy::default(), &None, &[]).unwrap();
+        assert_eq!(TrainFareType::Ticket, act.single_trip_adult_fare.train_fare_type);
+        assert_eq!(vec!["スマートEX の特急料金を適用します".to_string()], act.notes);
+    }
+
+    #[test]
+    fn ex_product_not_covered() {
+        let fare_query = FareQuery {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival: Hakata,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            ex_product: Some(ExProduct::ExReservation),
+            ..fare_query(date(1, 31))
+        };
+        let act = calc_fare_quote(&fare_query, &DiscountPolicy::default(), &None, &[]).err().unwrap();
+        assert_eq!("EX予約 の対象外区間です", act.to_string());
+    }
+
+    // EX サービスは在来線で乗り継ぐ区間には利用できない
+    #[test]
+    fn ex_product_with_connections() {
+        let fare_query = FareQuery {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: Some(ConventionalStation::Kakogawa),
+            },
+            ex_product: Some(ExProduct::ExReservation),
+            ..fare_query(date(2, 15))
+        };
+        let act = calc_fare_quote(&fare_query, &DiscountPolicy::default(), &None, &[]).err().unwrap();
+        assert_eq!("EX予約 は在来線で乗り継ぐ区間には利用できません", act.to_string());
+    }
+
+    // 特定区間運賃は支払い方法ごとに定め、適用したことを内訳に記録する
+    #[rstest]
+    #[case(PaymentMedium::Ticket, 1520, TrainFareType::Ticket)]
+    #[case(PaymentMedium::IcCard, 1518, TrainFareType::Ic)]
+    fn special_train_fare(#[case] payment_medium: PaymentMedium, #[case] exp: u64, #[case] exp_type: TrainFareType) {
+        let fare_query = FareQuery {
+            ride_section: RideSection {
+                departure: ShinOsaka,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            train: Hikari,
+            payment_medium,
+            ..fare_query(date(2, 10))
+        };
+        let act = calc_fare_quote(&fare_query, &DiscountPolicy::default(), &None, &[]).unwrap();
+        assert_eq!(exp, act.single_trip_adult_fare.train_fare.value.value);
+        assert_eq!(exp_type, act.single_trip_adult_fare.train_fare_type);
+        assert_eq!(SpecialFareApplication { train_fare: true, express_fare: false }, act.special_fare);
+    }
+
+    // 利用数はプロモーションを適用した場合に限り消費する
+    #[rstest]
+    #[case(date(2, 10), 2)]
+    #[case(date(1, 31), 0)]
+    fn consume(#[case] booking_date: NaiveDate, #[case] exp: usize) {
+        let fare_query = fare_query(booking_date);
+        let mut promotions = promotions();
+        let fare_quote =
+            calc_fare_quote(&fare_query, &DiscountPolicy::default(), &Some("EXHAYA".to_string()), &promotions).unwrap();
+        consume_promotion(&mut promotions, &fare_quote, &fare_query).unwrap();
+        assert_eq!(exp, promotions[0].used_count);
+    }
+}
@@ -1,4 +1,12 @@
+pub mod ex_express_fare;
 pub mod express_fare;
+pub mod fare_breakdown;
+pub mod fare_product;
+pub mod fare_quote;
+pub mod operator_revenue;
+pub mod passenger_fare;
+pub mod seat_availability;
 pub mod single_trip_adult_fare;
+pub mod special_section;
 pub mod total_fare;
 pub mod train_fare;
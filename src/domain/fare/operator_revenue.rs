@@ -0,0 +1,214 @@
+use crate::domain::base::conventional_line::{find_conventional_segment, ConventionalStation};
+use crate::domain::base::operator::Operator;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::base::shinkansen_line::split_by_line;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::fundamental::amount::Amount;
+use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+// 会社ごとの収入
+// total_fare は割引を適用した人数分の総料金のうち、その会社の分
+#[derive(PartialEq, Debug)]
+pub struct OperatorRevenue {
+    pub operator: Operator,
+    pub train_fare: TrainFare,
+    pub express_fare: ExpressFare,
+    pub total_fare: Amount,
+}
+
+// 会社ごとに乗車する営業キロを、乗車する順に返す ( 在来線の区間を含む )
+pub fn calc_operator_kilometers(ride_section: &RideSection) -> Vec<(Operator, f64)> {
+    let connection_kilometer = |connection: &ConventionalStation| {
+        let segment = find_conventional_segment(connection);
+        (segment.operator, segment.operating_kilometer.value)
+    };
+    let line_kilometers = split_by_line(ride_section).into_iter().map(|line_section| {
+        (line_section.line.get_operator(), line_section.ride_section.get_operation_kilometer().value)
+    });
+
+    let mut kilometers: Vec<(Operator, f64)> = vec![];
+    let all_kilometers = ride_section
+        .departure_connection
+        .iter()
+        .map(connection_kilometer)
+        .chain(line_kilometers)
+        .chain(ride_section.arrival_connection.iter().map(connection_kilometer));
+    for (operator, kilometer) in all_kilometers {
+        match kilometers.iter_mut().find(|(other, _)| *other == operator) {
+            Some((_, sum)) => *sum += kilometer,
+            None => kilometers.push((operator, kilometer)),
+        }
+    }
+    kilometers
+}
+
+// 運賃は会社ごとの営業キロの比で、特急料金は通常の特急料金の会社ごとの内訳の比で按分する
+// 商品や割引で金額が変わっても、会社ごとの負担の比は変えない
+// 総料金は会社ごとの運賃と特急料金の合計の比で按分する
+pub fn attribute_revenue(
+    train_fare: &TrainFare,
+    express_fare: &ExpressFare,
+    total_fare: &Amount,
+    ride_section: &RideSection,
+    regular_express_fares: &[(Operator, ExpressFare)],
+) -> anyhow::Result<Vec<OperatorRevenue>> {
+    let kilometers = calc_operator_kilometers(ride_section);
+    let operators = kilometers.iter().map(|(operator, _)| *operator).collect::<Vec<_>>();
+
+    let kilometer_weights = kilometers
+        .iter()
+        .map(|(_, kilometer)| OperatingKilometer { value: *kilometer }.to_tenths())
+        .collect::<Vec<_>>();
+    let train_fares = train_fare.value.apportion(&kilometer_weights)?;
+    let express_weights = operators
+        .iter()
+        .map(|operator| {
+            regular_express_fares
+                .iter()
+                .find(|(other, _)| other == operator)
+                .map_or(0, |(_, express_fare)| express_fare.value.value)
+        })
+        .collect::<Vec<_>>();
+    let express_fares = express_fare.value.apportion(&express_weights)?;
+    let total_weights = train_fares
+        .iter()
+        .zip(&express_fares)
+        .map(|(train_fare, express_fare)| train_fare.value + express_fare.value)
+        .collect::<Vec<_>>();
+    let total_fares = total_fare.apportion(&total_weights)?;
+
+    Ok(operators
+        .into_iter()
+        .zip(train_fares)
+        .zip(express_fares)
+        .zip(total_fares)
+        .map(|(((operator, train_fare), express_fare), total_fare)| OperatorRevenue {
+            operator,
+            train_fare: TrainFare { value: train_fare },
+            express_fare: ExpressFare { value: express_fare },
+            total_fare,
+        })
+        .collect())
+}
+
+// 複数の問い合わせの総料金を会社ごとに合計し、東の会社から順に返す
+// 運賃と特急料金は割引前の片道おとな 1 名分の単価で収入ではないため合計しない
+pub fn sum_operator_revenues(operator_revenues: &[Vec<OperatorRevenue>]) -> Vec<(Operator, Amount)> {
+    let mut sums: Vec<(Operator, Amount)> = vec![];
+    for operator_revenue in operator_revenues.iter().flatten() {
+        match sums.iter_mut().find(|(operator, _)| *operator == operator_revenue.operator) {
+            Some((_, sum)) => *sum = sum.clone() + operator_revenue.total_fare.clone(),
+            None => sums.push((operator_revenue.operator, operator_revenue.total_fare.clone())),
+        }
+    }
+    sums.sort_by_key(|(operator, _)| *operator);
+    sums
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::conventional_line::ConventionalStation::*;
+    use crate::domain::base::operator::Operator;
+    use crate::domain::base::operator::Operator::*;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::operator_revenue::{
+        attribute_revenue, calc_operator_kilometers, sum_operator_revenues, OperatorRevenue,
+    };
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::fundamental::amount::Amount;
+
+    fn revenue(operator: Operator, train_fare: u64, express_fare: u64, total_fare: u64) -> OperatorRevenue {
+        OperatorRevenue {
+            operator,
+            train_fare: TrainFare { value: Amount { value: train_fare } },
+            express_fare: ExpressFare { value: Amount { value: express_fare } },
+            total_fare: Amount { value: total_fare },
+        }
+    }
+
+    #[rstest]
+    #[case(None, Tokyo, ShinOsaka, None, vec![(Central, 553.0)])]
+    #[case(None, Himeji, Tokyo, None, vec![(West, 91.0), (Central, 553.0)])]
+    #[case(None, Tokyo, KagoshimaChuo, None, vec![(Central, 553.0), (West, 621.9), (Kyushu, 288.9)])]
+    // 在来線の区間は線路を持つ会社の営業キロとする ( 地方交通線も換算キロではなく営業キロ )
+    #[case(Some(Shinagawa), Tokyo, Himeji, Some(HonTatsuno), vec![(East, 6.8), (Central, 553.0), (West, 106.6)])]
+    fn operator_kilometers(
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] arrival_connection: Option<ConventionalStation>,
+        #[case] exp: Vec<(Operator, f64)>,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection, arrival_connection };
+        assert_eq!(exp, calc_operator_kilometers(&ride_section));
+    }
+
+    #[test]
+    fn attribute() {
+        let ride_section =
+            RideSection { departure: Tokyo, arrival: Himeji, departure_connection: None, arrival_connection: None };
+        let regular_express_fares = [
+            (Central, ExpressFare { value: Amount { value: 5711 } }),
+            (West, ExpressFare { value: Amount { value: 939 } }),
+        ];
+        assert_eq!(
+            vec![revenue(Central, 8596, 5711, 1316914), revenue(West, 1414, 939, 216586)],
+            attribute_revenue(
+                &TrainFare { value: Amount { value: 10010 } },
+                &ExpressFare { value: Amount { value: 6650 } },
+                &Amount { value: 1533500 },
+                &ride_section,
+                &regular_express_fares,
+            )
+            .unwrap()
+        );
+    }
+
+    // 在来線だけの会社には特急料金を按分しない
+    #[test]
+    fn attribute_with_connection() {
+        let ride_section = RideSection {
+            departure: Tokyo,
+            arrival: ShinOsaka,
+            departure_connection: Some(Shinagawa),
+            arrival_connection: None,
+        };
+        let regular_express_fares = [(Central, ExpressFare { value: Amount { value: 5490 } })];
+        assert_eq!(
+            vec![revenue(East, 108, 0, 108), revenue(Central, 8802, 5490, 14292)],
+            attribute_revenue(
+                &TrainFare { value: Amount { value: 8910 } },
+                &ExpressFare { value: Amount { value: 5490 } },
+                &Amount { value: 14400 },
+                &ride_section,
+                &regular_express_fares,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn sum() {
+        let operator_revenues = vec![
+            vec![revenue(Central, 8598, 5711, 14309), revenue(West, 1412, 939, 2351)],
+            vec![revenue(Kyushu, 1000, 2000, 3000), revenue(West, 100, 200, 300)],
+            vec![revenue(East, 108, 0, 108), revenue(Central, 8802, 5490, 14292)],
+        ];
+        assert_eq!(
+            vec![
+                (East, Amount { value: 108 }),
+                (Central, Amount { value: 28601 }),
+                (West, Amount { value: 2651 }),
+                (Kyushu, Amount { value: 3000 }),
+            ],
+            sum_operator_revenues(&operator_revenues)
+        );
+    }
+}
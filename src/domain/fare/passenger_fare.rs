@@ -0,0 +1,191 @@
+use crate::domain::discount::discount_policy::{apply_discounts, DiscountPolicy};
+use crate::domain::discount::individual_discount::{select_individual_discounts, IndividualDiscount, PassengerTarget};
+use crate::domain::fare::express_fare::{ExpressFare, EXPRESS_FARE_ROUNDING};
+use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::fundamental::amount::Amount;
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// 割引を適用したおとな 1 人あたりの片道料金
+pub fn calc_adult_fare(
+    single_trip_adult_fare: &SingleTripAdultFare,
+    individual_discounts: &[IndividualDiscount],
+    discount_policy: &DiscountPolicy,
+) -> (TrainFare, ExpressFare) {
+    apply_discounts(
+        single_trip_adult_fare.get_adult_fare(),
+        &select_individual_discounts(individual_discounts, PassengerTarget::Adult),
+        discount_policy,
+    )
+}
+
+// 割引を適用したこども 1 人あたりの片道料金
+// JR の規則に従い、おとなとこどもに共通の割引を適用したおとなの料金を運賃と特急料金ごとに折半して 10 円未満を切り捨てる
+// ただし IC 運賃は 1 円未満を切り捨てる
+// 指定席特急料金は通常期の料金を折半してから、シーズンによる差額をおとなと同額だけ加減する
+// こどもだけの割引は折半した後のこどもの料金に適用する
+pub fn calc_child_fare(
+    single_trip_adult_fare: &SingleTripAdultFare,
+    individual_discounts: &[IndividualDiscount],
+    discount_policy: &DiscountPolicy,
+) -> (TrainFare, ExpressFare) {
+    let (common_discounts, child_discounts): (Vec<_>, Vec<_>) = individual_discounts
+        .iter()
+        .filter(|discount| discount.passenger_target != PassengerTarget::Adult)
+        .cloned()
+        .partition(|discount| discount.passenger_target == PassengerTarget::All);
+
+    let season_adjustment = &single_trip_adult_fare.season_adjustment;
+    let (train_fare, express_fare) = single_trip_adult_fare.get_adult_fare();
+    let regular_adult_fare = (train_fare, ExpressFare { value: season_adjustment.revert(express_fare.value) });
+    let (train_fare, express_fare) = apply_discounts(regular_adult_fare, &common_discounts, discount_policy);
+    let child_fare = (
+        TrainFare { value: halve(train_fare.value, &single_trip_adult_fare.train_fare_type.get_rounding()) },
+        ExpressFare { value: season_adjustment.apply(halve(express_fare.value, &EXPRESS_FARE_ROUNDING)) },
+    );
+
+    apply_discounts(child_fare, &child_discounts, discount_policy)
+}
+
+fn halve(amount: Amount, rounding: &Rounding) -> Amount {
+    amount.multiply(&Fraction::new(1, 2), rounding)
+}
+
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::discount::discount_policy::DiscountPolicy;
+    use crate::domain::discount::group_discount::{create_group_discount_under_30, create_school_group_discount};
+    use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
+    use crate::domain::discount::round_trip_discount::create_round_trip_discount;
+    use crate::domain::fare::express_fare::{ExpressFare, SeasonAdjustment};
+    use crate::domain::fare::passenger_fare::{calc_adult_fare, calc_child_fare};
+    use crate::domain::fare::single_trip_adult_fare::{calc_single_trip_adult_fare, SingleTripAdultFare};
+    use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+
+    fn fare(train_fare: u64, express_fare: u64) -> (TrainFare, ExpressFare) {
+        (TrainFare { value: Amount { value: train_fare } }, ExpressFare { value: Amount { value: express_fare } })
+    }
+
+    // JR が公表しているこども運賃とこども特急料金 ( 2 月 1 日は通常期、 12 月 28 日は繁忙期、 1 月 20 日は閑散期 )
+    #[rstest]
+    #[case(ShinOsaka, Hikari, Reserved, 2, 1, 4450, 2740)]
+    #[case(ShinOsaka, Nozomi, Reserved, 2, 1, 4450, 2900)]
+    #[case(ShinOsaka, Nozomi, Free, 2, 1, 4450, 2480)]
+    #[case(ShinOsaka, Nozomi, Reserved, 12, 28, 4450, 3100)]
+    #[case(ShinOsaka, Nozomi, Reserved, 1, 20, 4450, 2700)]
+    #[case(Himeji, Hikari, Reserved, 2, 1, 5000, 2960)]
+    #[case(Himeji, Nozomi, Reserved, 2, 1, 5000, 3220)]
+    #[case(Himeji, Hikari, Free, 2, 1, 5000, 2690)]
+    #[case(Himeji, Nozomi, Reserved, 12, 28, 5000, 3420)]
+    fn published_child_fare(
+        #[case] arrival: Station,
+        #[case] train: Train,
+        #[case] seat_type: SeatType,
+        #[case] m: u32,
+        #[case] d: u32,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        let single_trip_adult_fare = calc_single_trip_adult_fare(
+            &RideSection { departure: Tokyo, arrival, departure_connection: None, arrival_connection: None },
+            &train,
+            &seat_type,
+            &DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() },
+        )
+        .unwrap();
+        assert_eq!(
+            fare(train_fare, express_fare),
+            calc_child_fare(&single_trip_adult_fare, &[], &DiscountPolicy::default())
+        );
+    }
+
+    #[rstest]
+    // 往復割引 : 10,010 x 0.9 => 9,000 を折半して 4,500
+    #[case(10010, 5920, vec![create_round_trip_discount()], 4500, 2960)]
+    // 団体割引 : 5,690 x 0.9 => 5,120 を折半して 2,560 ( こども料金 2,840 に割り引くと 2,550 になる )
+    #[case(8910, 5690, vec![create_group_discount_under_30(Fraction::percent(90))], 4000, 2560)]
+    // 学生団体割引 : こどもだけの割引は折半した 4,450 に適用して 2,220
+    #[case(8910, 5490, vec![create_school_group_discount(PassengerTarget::Child, Fraction::percent(50))], 2220, 2740)]
+    // おとなだけの割引はこどもに適用しない
+    #[case(8910, 5490, vec![create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70))], 4450, 2740)]
+    fn discounted_child_fare(
+        #[case] adult_train_fare: u64,
+        #[case] adult_express_fare: u64,
+        #[case] individual_discounts: Vec<IndividualDiscount>,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+    ) {
+        let single_trip_adult_fare = SingleTripAdultFare {
+            train_fare: TrainFare { value: Amount { value: adult_train_fare } },
+            train_fare_type: TrainFareType::Ticket,
+            express_fare: ExpressFare { value: Amount { value: adult_express_fare } },
+            season_adjustment: SeasonAdjustment::Unadjusted,
+        };
+        assert_eq!(
+            fare(train_fare, express_fare),
+            calc_child_fare(&single_trip_adult_fare, &individual_discounts, &DiscountPolicy::default())
+        );
+    }
+
+    // 通常期 5490 円の指定席特急料金を折半した 2740 円にシーズンによる差額を加減する
+    #[rstest]
+    #[case(5690, SeasonAdjustment::Increase(Amount { value: 200 }), 2940)]
+    #[case(5290, SeasonAdjustment::Decrease(Amount { value: 200 }), 2540)]
+    fn season_adjusted_child_fare(
+        #[case] adult_express_fare: u64,
+        #[case] season_adjustment: SeasonAdjustment,
+        #[case] express_fare: u64,
+    ) {
+        let single_trip_adult_fare = SingleTripAdultFare {
+            train_fare: TrainFare { value: Amount { value: 8910 } },
+            train_fare_type: TrainFareType::Ticket,
+            express_fare: ExpressFare { value: Amount { value: adult_express_fare } },
+            season_adjustment,
+        };
+        assert_eq!(fare(4450, express_fare), calc_child_fare(&single_trip_adult_fare, &[], &DiscountPolicy::default()));
+    }
+
+    // IC 運賃を折半したこども運賃は 1 円未満を切り捨てる
+    #[test]
+    fn ic_child_fare() {
+        let single_trip_adult_fare = SingleTripAdultFare {
+            train_fare: TrainFare { value: Amount { value: 8905 } },
+            train_fare_type: TrainFareType::Ic,
+            express_fare: ExpressFare { value: Amount { value: 5490 } },
+            season_adjustment: SeasonAdjustment::Unadjusted,
+        };
+        assert_eq!(fare(4452, 2740), calc_child_fare(&single_trip_adult_fare, &[], &DiscountPolicy::default()));
+    }
+
+    #[test]
+    fn adult_fare() {
+        let single_trip_adult_fare = SingleTripAdultFare {
+            train_fare: TrainFare { value: Amount { value: 8910 } },
+            train_fare_type: TrainFareType::Ticket,
+            express_fare: ExpressFare { value: Amount { value: 5490 } },
+            season_adjustment: SeasonAdjustment::Unadjusted,
+        };
+        let individual_discounts = [
+            create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70)),
+            create_school_group_discount(PassengerTarget::Child, Fraction::percent(50)),
+        ];
+        assert_eq!(
+            fare(6230, 5490),
+            calc_adult_fare(&single_trip_adult_fare, &individual_discounts, &DiscountPolicy::default())
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use crate::domain::base::departure_date::{DepartureDate, Season};
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+
+// 列車と座席の組み合わせが乗車日に利用できない理由
+// alternative_* には利用できる組み合わせのうち最も近いものを提案する
+#[derive(PartialEq, Debug)]
+pub enum SeatUnavailability {
+    // 繁忙期ののぞみは全車指定席で運転する
+    NozomiAllReserved { alternative_train: Train, alternative_seat_type: SeatType },
+}
+
+impl SeatUnavailability {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NozomiAllReserved { alternative_train, alternative_seat_type } => format!(
+                "繁忙期ののぞみは全車指定席のため自由席は利用できません ( {}{} をご利用ください )",
+                alternative_train.describe(),
+                alternative_seat_type.describe()
+            ),
+        }
+    }
+}
+
+// 繁忙期ののぞみ自由席は存在しないため料金を算出する前に弾く
+// 列車を変えずに済むのぞみ指定席を最も近い代替として提案する
+pub fn judge_seat_availability(
+    train: &Train,
+    seat_type: &SeatType,
+    departure_date: &DepartureDate,
+) -> Result<(), SeatUnavailability> {
+    match (train, seat_type, departure_date.get_season()) {
+        (Train::Nozomi, SeatType::Free, Season::Peak) => Err(SeatUnavailability::NozomiAllReserved {
+            alternative_train: Train::Nozomi,
+            alternative_seat_type: SeatType::Reserved,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::fare::seat_availability::{judge_seat_availability, SeatUnavailability};
+
+    // 2 月 1 日は通常期、 1 月 10 日と 12 月 25 日は繁忙期、 1 月 20 日は閑散期
+    #[rstest]
+    #[case(Nozomi, Reserved, 1, 10)]
+    #[case(Nozomi, Free, 2, 1)]
+    #[case(Nozomi, Free, 1, 20)]
+    #[case(Nozomi, Free, 12, 24)]
+    #[case(Hikari, Free, 1, 10)]
+    #[case(Hikari, Reserved, 12, 25)]
+    fn available(#[case] train: Train, #[case] seat_type: SeatType, #[case] m: u32, #[case] d: u32) {
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
+        assert_eq!(Ok(()), judge_seat_availability(&train, &seat_type, &departure_date));
+    }
+
+    #[rstest]
+    #[case(1, 10)]
+    #[case(12, 25)]
+    #[case(12, 31)]
+    fn unavailable(#[case] m: u32, #[case] d: u32) {
+        let departure_date = DepartureDate { value: NaiveDate::from_ymd_opt(2024, m, d).unwrap() };
+        assert_eq!(
+            Err(SeatUnavailability::NozomiAllReserved { alternative_train: Nozomi, alternative_seat_type: Reserved }),
+            judge_seat_availability(&Nozomi, &Free, &departure_date)
+        );
+    }
+
+    #[test]
+    fn describe() {
+        let sut = SeatUnavailability::NozomiAllReserved { alternative_train: Nozomi, alternative_seat_type: Reserved };
+        assert_eq!(
+            "繁忙期ののぞみは全車指定席のため自由席は利用できません ( のぞみ指定席 をご利用ください )",
+            sut.describe()
+        );
+    }
+}
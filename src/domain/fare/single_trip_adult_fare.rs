@@ -4,25 +4,22 @@ use crate::domain::base::ride_section::RideSection;
 use crate::domain::base::seat_type::SeatType;
 use crate::domain::base::train::Train;
 
-use crate::domain::fare::express_fare::{calc_express_fare, ExpressFare};
-use crate::domain::fare::train_fare::{calc_train_fare, TrainFare};
+use crate::domain::fare::express_fare::{calc_express_fare, judge_season_adjustment, ExpressFare, SeasonAdjustment};
+use crate::domain::fare::train_fare::{calc_train_fare, TrainFare, TrainFareType};
 
 pub struct SingleTripAdultFare {
     pub train_fare: TrainFare,
+    // 運賃がきっぷと IC のどちらか ( こども運賃の端数処理が異なる )
+    pub train_fare_type: TrainFareType,
     pub express_fare: ExpressFare,
+    // express_fare に含まれるシーズンによる差額
+    pub season_adjustment: SeasonAdjustment,
 }
 
 impl SingleTripAdultFare {
     pub fn get_adult_fare(&self) -> (TrainFare, ExpressFare) {
         (self.train_fare.clone(), self.express_fare.clone())
     }
-
-    pub fn get_child_fare(&self) -> (TrainFare, ExpressFare) {
-        (
-            TrainFare { value: self.train_fare.clone().value * 0.5 },
-            ExpressFare { value: self.express_fare.clone().value * 0.5 },
-        )
-    }
 }
 
 pub fn calc_single_trip_adult_fare(
@@ -30,8 +27,9 @@ pub fn calc_single_trip_adult_fare(
     train: &Train,
     seat_type: &SeatType,
     departure_date: &DepartureDate,
-) -> SingleTripAdultFare {
-    let train_fare = calc_train_fare(ride_section);
-    let express_fare = calc_express_fare(train, seat_type, ride_section, departure_date);
-    SingleTripAdultFare { train_fare, express_fare }
+) -> anyhow::Result<SingleTripAdultFare> {
+    let train_fare = calc_train_fare(ride_section)?;
+    let express_fare = calc_express_fare(train, seat_type, ride_section, departure_date)?;
+    let season_adjustment = judge_season_adjustment(seat_type, departure_date);
+    Ok(SingleTripAdultFare { train_fare, train_fare_type: TrainFareType::Ticket, express_fare, season_adjustment })
 }
@@ -0,0 +1,183 @@
+use crate::domain::base::ride_section::{RideSection, Station};
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
+use crate::fundamental::amount::Amount;
+
+// 営業キロによる計算より安く定められた運賃 ( 特定区間運賃 ) と特急料金 ( 特定特急料金 ) の区間
+// 定めのない方は通常どおり算出する
+#[derive(PartialEq, Debug)]
+pub struct SpecialSection {
+    pub station_pair: (Station, Station),
+    pub train_fare: Option<TrainFare>,
+    // IC 運賃の設定がある区間の 1 円単位の IC 運賃 ( 定めがなければ IC カードでもきっぷの運賃 )
+    pub ic_train_fare: Option<TrainFare>,
+    // 座席の種類ごとに定め、定めのない座席の種類は通常の特急料金
+    pub express_fares: Vec<(SeatType, ExpressFare)>,
+}
+
+// 特定区間運賃と特定特急料金を適用したか
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Default)]
+pub struct SpecialFareApplication {
+    pub train_fare: bool,
+    pub express_fare: bool,
+}
+
+pub fn get_special_sections() -> Vec<SpecialSection> {
+    vec![
+        // 大阪近郊の特定区間運賃 ( 営業キロ 91.0 km による運賃は IC 1,694 円、きっぷ 1,690 円 )
+        // 税抜 1,380 円に消費税を加えた IC 運賃と、その 10 円未満を四捨五入したきっぷの運賃
+        SpecialSection {
+            station_pair: (Station::ShinOsaka, Station::Himeji),
+            train_fare: Some(TrainFare { value: Amount { value: 1520 } }),
+            ic_train_fare: Some(TrainFare { value: Amount { value: 1518 } }),
+            express_fares: vec![],
+        },
+    ]
+}
+
+fn find_special_section<'a>(
+    ride_section: &RideSection,
+    special_sections: &'a [SpecialSection],
+) -> Option<&'a SpecialSection> {
+    let (departure, arrival) = ride_section.get_station_pair();
+    special_sections.iter().find(|special_section| special_section.station_pair == (*departure, *arrival))
+}
+
+// 通常の運賃の計算より先に参照し、適用した運賃の種類とともに返す
+// 通常の運賃が IC 運賃になる場合は、特定区間の IC 運賃の定めがあればそれを、なければきっぷの運賃を適用する
+// 在来線で乗り継ぐ場合は運賃の区間が特定区間と異なるため適用しない
+pub fn judge_special_train_fare(
+    ride_section: &RideSection,
+    train_fare_type: &TrainFareType,
+    special_sections: &[SpecialSection],
+) -> Option<(TrainFare, TrainFareType)> {
+    if ride_section.has_connections() {
+        return None;
+    }
+    let special_section = find_special_section(ride_section, special_sections)?;
+    let train_fare = special_section.train_fare.clone()?;
+    match (train_fare_type, &special_section.ic_train_fare) {
+        (TrainFareType::Ic, Some(ic_train_fare)) => Some((ic_train_fare.clone(), TrainFareType::Ic)),
+        _ => Some((train_fare, TrainFareType::Ticket)),
+    }
+}
+
+// 通常の特急料金の計算より先に参照する
+pub fn judge_special_express_fare(
+    ride_section: &RideSection,
+    seat_type: &SeatType,
+    special_sections: &[SpecialSection],
+) -> Option<ExpressFare> {
+    find_special_section(ride_section, special_sections)?
+        .express_fares
+        .iter()
+        .find(|(special_seat_type, _)| special_seat_type == seat_type)
+        .map(|(_, express_fare)| express_fare.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::conventional_line::ConventionalStation::*;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::special_section::{
+        get_special_sections, judge_special_express_fare, judge_special_train_fare, SpecialSection,
+    };
+    use crate::domain::fare::train_fare::TrainFareType::{Ic, Ticket};
+    use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
+    use crate::fundamental::amount::Amount;
+
+    fn special_sections() -> Vec<SpecialSection> {
+        vec![
+            SpecialSection {
+                station_pair: (Tokyo, ShinOsaka),
+                train_fare: Some(TrainFare { value: Amount { value: 8500 } }),
+                ic_train_fare: None,
+                express_fares: vec![],
+            },
+            SpecialSection {
+                station_pair: (ShinOsaka, Himeji),
+                train_fare: None,
+                ic_train_fare: None,
+                express_fares: vec![
+                    (Free, ExpressFare { value: Amount { value: 1000 } }),
+                    (Reserved, ExpressFare { value: Amount { value: 1530 } }),
+                ],
+            },
+            SpecialSection {
+                station_pair: (Himeji, Hakata),
+                train_fare: None,
+                ic_train_fare: None,
+                express_fares: vec![(Free, ExpressFare { value: Amount { value: 4000 } })],
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case(Tokyo, ShinOsaka, None, Ticket, Some(8500))]
+    #[case(ShinOsaka, Tokyo, None, Ticket, Some(8500))]
+    // IC 運賃の定めがなければ IC カードでもきっぷの運賃
+    #[case(ShinOsaka, Tokyo, None, Ic, Some(8500))]
+    #[case(ShinOsaka, Himeji, None, Ticket, None)]
+    #[case(Tokyo, Himeji, None, Ticket, None)]
+    // 在来線で乗り継ぐ場合は通常の運賃
+    #[case(Tokyo, ShinOsaka, Some(Shinagawa), Ticket, None)]
+    fn train_fare(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] train_fare_type: TrainFareType,
+        #[case] exp: Option<u64>,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection, arrival_connection: None };
+        assert_eq!(
+            exp.map(|value| (TrainFare { value: Amount { value } }, Ticket)),
+            judge_special_train_fare(&ride_section, &train_fare_type, &special_sections())
+        );
+    }
+
+    #[rstest]
+    #[case(Himeji, ShinOsaka, Free, Some(1000))]
+    #[case(Himeji, ShinOsaka, Reserved, Some(1530))]
+    // 定めのない座席の種類は通常の特急料金
+    #[case(Himeji, Hakata, Reserved, None)]
+    #[case(Tokyo, ShinOsaka, Free, None)]
+    #[case(Tokyo, Himeji, Free, None)]
+    fn express_fare(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] seat_type: SeatType,
+        #[case] exp: Option<u64>,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            exp.map(|value| ExpressFare { value: Amount { value } }),
+            judge_special_express_fare(&ride_section, &seat_type, &special_sections())
+        );
+    }
+
+    #[rstest]
+    #[case(ShinOsaka, Himeji, Ticket, Some((1520, Ticket)))]
+    #[case(Himeji, ShinOsaka, Ticket, Some((1520, Ticket)))]
+    #[case(ShinOsaka, Himeji, Ic, Some((1518, Ic)))]
+    #[case(Tokyo, ShinOsaka, Ticket, None)]
+    fn production_train_fare(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] train_fare_type: TrainFareType,
+        #[case] exp: Option<(u64, TrainFareType)>,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            exp.map(|(value, train_fare_type)| (TrainFare { value: Amount { value } }, train_fare_type)),
+            judge_special_train_fare(&ride_section, &train_fare_type, &get_special_sections())
+        );
+    }
+}
@@ -3,10 +3,11 @@ use ReserveType::{RoundTrip, SingleTrip};
 use crate::domain::base::number_of_passengers::NumberOfPassengers;
 use crate::domain::base::reserve_type::ReserveType;
 
+use crate::domain::discount::discount_policy::DiscountPolicy;
 use crate::domain::discount::individual_discount::IndividualDiscount;
 use crate::domain::discount::overall_discount::OverallDiscount;
-use crate::domain::discount::overall_discount::OverallDiscount::GroupDiscountMore31;
 
+use crate::domain::fare::passenger_fare::{calc_adult_fare, calc_child_fare};
 use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
 
 use crate::fundamental::amount::Amount;
@@ -17,33 +18,35 @@ pub fn calc_total_fare(
     single_trip_adult_fare: &SingleTripAdultFare,
     overall_discount: &Option<OverallDiscount>,
     individual_discounts: &[IndividualDiscount],
+    discount_policy: &DiscountPolicy,
 ) -> Amount {
+    // 全体割引で無料になる旅客は人数に含めない
+    let free_passengers = match overall_discount {
+        Some(overall_discount) => overall_discount.get_free_passengers(),
+        None => NumberOfPassengers { adult: 0, child: 0 },
+    };
+
     let (total_adult_train_free, total_adult_express_fee) = {
-        let (discounted_train_fare, discounted_express_fare) = individual_discounts
-            .iter()
-            .fold(single_trip_adult_fare.get_adult_fare(), |acc, discount| discount.apply(acc));
+        let (discounted_train_fare, discounted_express_fare) =
+            calc_adult_fare(single_trip_adult_fare, individual_discounts, discount_policy);
 
-        let count = match overall_discount {
-            Some(GroupDiscountMore31 { free_count }) => number_of_passengers.adult - free_count,
-            _ => number_of_passengers.adult,
-        } as f32;
+        let count = number_of_passengers.adult - free_passengers.adult;
 
         (discounted_train_fare.value * count, discounted_express_fare.value * count)
     };
 
     let (total_child_train_free, total_child_express_fee) = {
-        let (discounted_train_fare, discounted_express_fare) = individual_discounts
-            .iter()
-            .fold(single_trip_adult_fare.get_child_fare(), |acc, discount| discount.apply(acc));
+        let (discounted_train_fare, discounted_express_fare) =
+            calc_child_fare(single_trip_adult_fare, individual_discounts, discount_policy);
 
-        let count = number_of_passengers.child as f32;
+        let count = number_of_passengers.child - free_passengers.child;
 
         (discounted_train_fare.value * count, discounted_express_fare.value * count)
     };
 
     let way = match reserve_type {
-        SingleTrip => 1.0,
-        RoundTrip => 2.0,
+        SingleTrip => 1,
+        RoundTrip => 2,
     };
 
     (total_adult_train_free + total_adult_express_fee + total_child_train_free + total_child_express_fee) * way
@@ -54,20 +57,21 @@ pub fn calc_total_fare(
 mod tests {
     use rstest::rstest;
 
-    use IndividualDiscount::RoundTripDiscount;
-
     use crate::domain::base::number_of_passengers::NumberOfPassengers;
     use crate::domain::base::reserve_type::ReserveType;
     use crate::domain::base::reserve_type::ReserveType::*;
-    use crate::domain::discount::individual_discount::IndividualDiscount;
-    use crate::domain::discount::individual_discount::IndividualDiscount::GroupDiscountUnder30;
+    use crate::domain::discount::discount_policy::DiscountPolicy;
+    use crate::domain::discount::group_discount::{create_group_discount_under_30, create_school_group_discount};
+    use crate::domain::discount::individual_discount::{IndividualDiscount, PassengerTarget};
     use crate::domain::discount::overall_discount::OverallDiscount;
-    use crate::domain::discount::overall_discount::OverallDiscount::GroupDiscountMore31;
-    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::discount::overall_discount::OverallDiscount::LargeGroupDiscount;
+    use crate::domain::discount::round_trip_discount::create_round_trip_discount;
+    use crate::domain::fare::express_fare::{ExpressFare, SeasonAdjustment};
     use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
     use crate::domain::fare::total_fare::calc_total_fare;
-    use crate::domain::fare::train_fare::TrainFare;
+    use crate::domain::fare::train_fare::{TrainFare, TrainFareType};
     use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
 
     #[rstest]
     // おとな 1 人、こども 0 人
@@ -79,15 +83,49 @@ mod tests {
     // 往復
     #[case(RoundTrip, 1, 0, 8910, 5490, None, None, None, 28800)]
     // 個別割引 - 往復割引
-    #[case(RoundTrip, 1, 0, 10010, 5920, None, Some(RoundTripDiscount), None, 29840)]
+    #[case(RoundTrip, 1, 0, 10010, 5920, None, Some(create_round_trip_discount()), None, 29840)]
     // 個別割引 - 団体割引
-    #[case(SingleTrip, 10, 0, 8910, 5490, None, None, Some(GroupDiscountUnder30 { discount_rate: 0.9 }), 129500)]
+    #[case(
+        SingleTrip,
+        10,
+        0,
+        8910,
+        5490,
+        None,
+        None,
+        Some(create_group_discount_under_30(Fraction::percent(90))),
+        129500
+    )]
     // 個別割引 - 往復割引 & 団体割引
-    #[case(RoundTrip, 10, 0, 10010, 5920, None, Some(RoundTripDiscount), Some(GroupDiscountUnder30 { discount_rate: 0.85 }), 253600)]
+    #[case(
+        RoundTrip,
+        10,
+        0,
+        10010,
+        5920,
+        None,
+        Some(create_round_trip_discount()),
+        Some(create_group_discount_under_30(Fraction::percent(85))),
+        253600
+    )]
+    // 個別割引 - 学生団体割引 ( おとなとこどもで割引率が異なる )
+    #[case(
+        SingleTrip,
+        2,
+        8,
+        8910,
+        5490,
+        None,
+        Some(create_school_group_discount(PassengerTarget::Adult, Fraction::percent(70))),
+        Some(create_school_group_discount(PassengerTarget::Child, Fraction::percent(50))),
+        63120
+    )]
     // 全体割引 - 団体割引
-    #[case(SingleTrip, 50, 0, 8910, 5490, Some(GroupDiscountMore31 {free_count: 1}), None, None, 705600)]
+    #[case(SingleTrip, 50, 0, 8910, 5490, Some(LargeGroupDiscount { min_passengers: 31, free_passengers: NumberOfPassengers { adult: 1, child: 0 } }), None, None, 705600)]
+    // 全体割引 - 団体割引 ( おとながいないためこどもが無料 )
+    #[case(SingleTrip, 0, 31, 8910, 5490, Some(LargeGroupDiscount { min_passengers: 31, free_passengers: NumberOfPassengers { adult: 0, child: 1 } }), None, None, 215700)]
     // 個別割引 - 往復割引 & 全体割引 - 団体割引
-    #[case(RoundTrip, 100, 0, 10010, 5920, Some(GroupDiscountMore31 {free_count: 2}), Some(RoundTripDiscount), None, 2924320)]
+    #[case(RoundTrip, 100, 0, 10010, 5920, Some(LargeGroupDiscount { min_passengers: 31, free_passengers: NumberOfPassengers { adult: 2, child: 0 } }), Some(create_round_trip_discount()), None, 2924320)]
     fn test_calc_total_fare(
         #[case] reserve_type: ReserveType,
         #[case] adult: usize,
@@ -102,7 +140,9 @@ mod tests {
         let number_of_passengers = NumberOfPassengers { adult, child };
         let single_trip_adult_fare = SingleTripAdultFare {
             train_fare: TrainFare { value: Amount { value: train_fare } },
+            train_fare_type: TrainFareType::Ticket,
             express_fare: ExpressFare { value: Amount { value: express_fare } },
+            season_adjustment: SeasonAdjustment::Unadjusted,
         };
         assert_eq!(
             Amount { value: exp },
@@ -117,6 +157,7 @@ mod tests {
                     (None, Some(discount2)) => vec![discount2],
                     (None, None) => vec![],
                 },
+                &DiscountPolicy::default(),
             )
         );
     }
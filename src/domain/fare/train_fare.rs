@@ -1,38 +1,401 @@
-use crate::domain::base::ride_section::{RideSection, Station};
+use anyhow::bail;
 
+use crate::domain::base::conventional_line::find_conventional_segment;
+use crate::domain::base::operator::Operator;
+use crate::domain::base::payment_medium::PaymentMedium;
+use crate::domain::base::ride_section::{RideSection, Station};
+use crate::domain::base::shinkansen_line::split_by_line;
+use crate::domain::fare::special_section::{get_special_sections, judge_special_train_fare};
 use crate::fundamental::amount::Amount;
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::operating_kilometer::OperatingKilometer;
+use crate::fundamental::rounding::{Rounding, RoundingMode, RoundingUnit};
 
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct TrainFare {
     pub value: Amount,
 }
 
-pub fn calc_train_fare(ride_section: &RideSection) -> TrainFare {
-    match ride_section.get_station_pair() {
-        (Station::Tokyo, Station::ShinOsaka) => TrainFare { value: Amount { value: 8910 } },
-        (Station::Tokyo, Station::Himeji) => TrainFare { value: Amount { value: 10010 } },
-        _ => panic!("unexpected ride section"),
+// 適用した運賃の種類
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum TrainFareType {
+    Ticket,
+    Ic,
+}
+
+// 区間ごとの運賃
+// IC 運賃は IC カードの利用エリア内で完結する区間にだけ設定される
+// IC 運賃の設定がある区間は 1 円単位の IC 運賃を、ない区間はきっぷの運賃を base_fare に記載する
+pub struct TrainFareEntry {
+    pub station_pair: (Station, Station),
+    pub base_fare: Amount,
+    pub ic_fare_available: bool,
+}
+
+impl TrainFareType {
+    // 割合を掛けた運賃 ( こども運賃など ) の端数処理
+    // IC 運賃は 1 円単位で、きっぷの運賃は 10 円未満を切り捨てる
+    pub fn get_rounding(&self) -> Rounding {
+        match self {
+            Self::Ticket => TRAIN_FARE_ROUNDING,
+            Self::Ic => IC_FARE_ROUNDING,
+        }
     }
 }
 
+const TRAIN_FARE_ROUNDING: Rounding = Rounding::FLOOR_TO_TEN;
+const IC_FARE_ROUNDING: Rounding = Rounding { mode: RoundingMode::Floor, unit: RoundingUnit::One };
+// IC 運賃の設定がある区間のきっぷの運賃は IC 運賃の 10 円未満を四捨五入する
+const TICKET_FARE_ROUNDING: Rounding = Rounding { mode: RoundingMode::Round, unit: RoundingUnit::Ten };
+
+pub fn get_train_fare_table() -> Vec<TrainFareEntry> {
+    vec![
+        TrainFareEntry {
+            station_pair: (Station::Tokyo, Station::ShinOsaka),
+            base_fare: Amount { value: 8910 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Tokyo, Station::Himeji),
+            base_fare: Amount { value: 10010 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Tokyo, Station::Hakata),
+            base_fare: Amount { value: 14080 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::ShinOsaka, Station::Himeji),
+            // 営業キロによる運賃 ( 税抜 1,540 円に消費税を加えた IC 運賃、きっぷは 1,690 円 )
+            // 特定区間運賃の定めがあるため、実際には special_section の運賃を適用する
+            base_fare: Amount { value: 1694 },
+            ic_fare_available: true,
+        },
+        TrainFareEntry {
+            station_pair: (Station::ShinOsaka, Station::Hakata),
+            base_fare: Amount { value: 9790 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Himeji, Station::Hakata),
+            base_fare: Amount { value: 8580 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Tokyo, Station::KagoshimaChuo),
+            base_fare: Amount { value: 17380 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::ShinOsaka, Station::KagoshimaChuo),
+            base_fare: Amount { value: 13000 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Himeji, Station::KagoshimaChuo),
+            base_fare: Amount { value: 12100 },
+            ic_fare_available: false,
+        },
+        TrainFareEntry {
+            station_pair: (Station::Hakata, Station::KagoshimaChuo),
+            base_fare: Amount { value: 5390 },
+            ic_fare_available: false,
+        },
+    ]
+}
+
+// 本州 3 社の幹線の営業キロ帯ごとの運賃 ( 下限, 上限, 運賃 )
+// 在来線で乗り継ぐ区間は駅の組み合わせの運賃がないため、営業キロ帯から運賃を求める
+// 東京から鹿児島中央までの営業キロを含む帯までを載せる
+const TRUNK_LINE_FARE_BANDS: [(f64, f64, u64); 63] = [
+    (1.0, 3.0, 150),
+    (4.0, 6.0, 190),
+    (7.0, 10.0, 200),
+    (11.0, 15.0, 240),
+    (16.0, 20.0, 330),
+    (21.0, 25.0, 420),
+    (26.0, 30.0, 510),
+    (31.0, 35.0, 590),
+    (36.0, 40.0, 680),
+    (41.0, 45.0, 770),
+    (46.0, 50.0, 860),
+    (51.0, 60.0, 990),
+    (61.0, 70.0, 1170),
+    (71.0, 80.0, 1340),
+    (81.0, 90.0, 1520),
+    (91.0, 100.0, 1690),
+    (101.0, 120.0, 1980),
+    (121.0, 140.0, 2310),
+    (141.0, 160.0, 2640),
+    (161.0, 180.0, 3080),
+    (181.0, 200.0, 3410),
+    (201.0, 220.0, 3740),
+    (221.0, 240.0, 4070),
+    (241.0, 260.0, 4510),
+    (261.0, 280.0, 4840),
+    (281.0, 300.0, 5170),
+    (301.0, 320.0, 5500),
+    (321.0, 340.0, 5720),
+    (341.0, 360.0, 6050),
+    (361.0, 380.0, 6380),
+    (381.0, 400.0, 6600),
+    (401.0, 420.0, 6930),
+    (421.0, 440.0, 7150),
+    (441.0, 460.0, 7480),
+    (461.0, 480.0, 7700),
+    (481.0, 500.0, 8030),
+    (501.0, 520.0, 8360),
+    (521.0, 540.0, 8580),
+    (541.0, 560.0, 8910),
+    (561.0, 580.0, 9130),
+    (581.0, 600.0, 9460),
+    (601.0, 640.0, 9790),
+    (641.0, 680.0, 10010),
+    (681.0, 720.0, 10340),
+    (721.0, 760.0, 10670),
+    (761.0, 800.0, 11000),
+    (801.0, 840.0, 11330),
+    (841.0, 880.0, 11550),
+    (881.0, 920.0, 11880),
+    (921.0, 960.0, 12210),
+    (961.0, 1000.0, 12540),
+    (1001.0, 1040.0, 12870),
+    (1041.0, 1080.0, 13200),
+    (1081.0, 1120.0, 13420),
+    (1121.0, 1160.0, 13750),
+    (1161.0, 1200.0, 14080),
+    (1201.0, 1240.0, 14410),
+    (1241.0, 1280.0, 14740),
+    (1281.0, 1320.0, 15070),
+    (1321.0, 1360.0, 15400),
+    (1361.0, 1400.0, 15730),
+    (1401.0, 1440.0, 16060),
+    (1441.0, 1480.0, 16390),
+];
+
+// 1 km 未満の端数は 1 km に切り上げて営業キロ帯を判定する
+pub fn calc_train_fare_by_kilometer(fare_kilometer: &OperatingKilometer) -> anyhow::Result<TrainFare> {
+    let kilometer = fare_kilometer.value.ceil();
+    let Some((_, _, fare)) =
+        TRUNK_LINE_FARE_BANDS.iter().find(|(lower, upper, _)| *lower <= kilometer && kilometer <= *upper)
+    else {
+        bail!("運賃表にない営業キロです ( {} km )", fare_kilometer.value);
+    };
+    Ok(TrainFare { value: Amount { value: *fare } })
+}
+
+// きっぷの運賃
+// 特定区間運賃の定めがあれば営業キロによる運賃より優先する
+pub fn calc_train_fare(ride_section: &RideSection) -> anyhow::Result<TrainFare> {
+    if let Some((train_fare, _)) =
+        judge_special_train_fare(ride_section, &TrainFareType::Ticket, &get_special_sections())
+    {
+        return Ok(train_fare);
+    }
+    let (train_fare, _) = calc_train_fare_by_medium(ride_section, &PaymentMedium::Ticket, &get_train_fare_table())?;
+    Ok(train_fare)
+}
+
+// IC カードで支払う場合は IC 運賃が設定された区間に限り IC 運賃を適用する
+// 在来線で乗り継ぐ場合は IC 運賃の設定がないため、在来線を含む営業キロからきっぷの運賃を求める
+pub fn calc_train_fare_by_medium(
+    ride_section: &RideSection,
+    payment_medium: &PaymentMedium,
+    train_fare_table: &[TrainFareEntry],
+) -> anyhow::Result<(TrainFare, TrainFareType)> {
+    if ride_section.has_connections() {
+        if includes_kyushu(ride_section) {
+            bail!("JR九州の区間を含む在来線の乗り継ぎは運賃を算出できません");
+        }
+        return Ok((calc_train_fare_by_kilometer(&ride_section.get_fare_kilometer())?, TrainFareType::Ticket));
+    }
+    let (departure, arrival) = ride_section.get_station_pair();
+    let entry = train_fare_table
+        .iter()
+        .find(|entry| entry.station_pair == (*departure, *arrival))
+        .unwrap_or_else(|| panic!("unexpected ride section"));
+
+    let base_fare = entry.base_fare.clone();
+    Ok(match (payment_medium, entry.ic_fare_available) {
+        (PaymentMedium::IcCard, true) => (TrainFare { value: base_fare }, TrainFareType::Ic),
+        (_, true) => {
+            let value = Amount { value: TICKET_FARE_ROUNDING.round(&Fraction::integer(base_fare.value)) };
+            (TrainFare { value }, TrainFareType::Ticket)
+        }
+        (_, false) => (TrainFare { value: base_fare }, TrainFareType::Ticket),
+    })
+}
+
+// JR 九州は本州 3 社と運賃表が異なり、地方交通線には換算キロではなく擬制キロを用いる
+// 営業キロ帯の運賃は本州 3 社の幹線に限るため、JR 九州の新幹線や在来線を含む乗り継ぎは扱わない
+fn includes_kyushu(ride_section: &RideSection) -> bool {
+    let connections = [ride_section.departure_connection, ride_section.arrival_connection];
+    split_by_line(ride_section).iter().any(|line_section| line_section.line.get_operator() == Operator::Kyushu)
+        || connections
+            .iter()
+            .flatten()
+            .any(|connection| find_conventional_segment(connection).operator == Operator::Kyushu)
+}
+
 #[cfg(test)]
 mod tests {
 
     use rstest::rstest;
 
+    use crate::domain::base::payment_medium::PaymentMedium;
+    use crate::domain::base::payment_medium::PaymentMedium::*;
     use crate::domain::base::ride_section::Station::*;
     use crate::domain::base::ride_section::{RideSection, Station};
 
-    use crate::domain::fare::train_fare::{calc_train_fare, TrainFare};
+    use crate::domain::base::conventional_line::ConventionalStation;
+    use crate::domain::base::conventional_line::ConventionalStation::*;
+    use crate::domain::fare::train_fare::{
+        calc_train_fare, calc_train_fare_by_kilometer, calc_train_fare_by_medium, get_train_fare_table, TrainFare,
+        TrainFareEntry, TrainFareType,
+    };
     use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::operating_kilometer::OperatingKilometer;
 
     #[rstest]
     #[case(Tokyo, ShinOsaka, 8910)]
     #[case(ShinOsaka, Tokyo, 8910)]
     #[case(Tokyo, Himeji, 10010)]
     #[case(Himeji, Tokyo, 10010)]
+    #[case(Tokyo, Hakata, 14080)]
+    #[case(Himeji, ShinOsaka, 1520)]
+    #[case(ShinOsaka, Hakata, 9790)]
+    #[case(Hakata, Himeji, 8580)]
+    #[case(KagoshimaChuo, Tokyo, 17380)]
+    #[case(Hakata, KagoshimaChuo, 5390)]
     fn test_create_train_fare(#[case] departure: Station, #[case] arrival: Station, #[case] exp: u64) {
-        let ride_section = RideSection { departure, arrival };
-        assert_eq!(TrainFare { value: Amount { value: exp } }, calc_train_fare(&ride_section));
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(TrainFare { value: Amount { value: exp } }, calc_train_fare(&ride_section).unwrap());
+    }
+
+    #[rstest]
+    // IC 運賃の設定がない区間はどの支払い方法でもきっぷの運賃
+    #[case(Ticket, Tokyo, Himeji, 10010, TrainFareType::Ticket)]
+    #[case(IcCard, Tokyo, Himeji, 10010, TrainFareType::Ticket)]
+    // IC 運賃の設定がある区間はきっぷの運賃だけ 10 円未満を四捨五入する
+    #[case(Ticket, Tokyo, ShinOsaka, 8910, TrainFareType::Ticket)]
+    #[case(IcCard, ShinOsaka, Tokyo, 8905, TrainFareType::Ic)]
+    fn by_medium(
+        #[case] payment_medium: PaymentMedium,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] exp: u64,
+        #[case] exp_type: TrainFareType,
+    ) {
+        let train_fare_table = [
+            TrainFareEntry {
+                station_pair: (Tokyo, ShinOsaka),
+                base_fare: Amount { value: 8905 },
+                ic_fare_available: true,
+            },
+            TrainFareEntry {
+                station_pair: (Tokyo, Himeji),
+                base_fare: Amount { value: 10010 },
+                ic_fare_available: false,
+            },
+        ];
+        let ride_section = RideSection { departure, arrival, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            (TrainFare { value: Amount { value: exp } }, exp_type),
+            calc_train_fare_by_medium(&ride_section, &payment_medium, &train_fare_table).unwrap()
+        );
+    }
+
+    // 運賃表の IC 運賃は 1 円単位で、きっぷの運賃と異なる
+    #[rstest]
+    #[case(Ticket, 1690, TrainFareType::Ticket)]
+    #[case(IcCard, 1694, TrainFareType::Ic)]
+    fn by_medium_of_fare_table(
+        #[case] payment_medium: PaymentMedium,
+        #[case] exp: u64,
+        #[case] exp_type: TrainFareType,
+    ) {
+        let ride_section =
+            RideSection { departure: ShinOsaka, arrival: Himeji, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            (TrainFare { value: Amount { value: exp } }, exp_type),
+            calc_train_fare_by_medium(&ride_section, &payment_medium, &get_train_fare_table()).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(553.0, 8910)]
+    #[case(560.0, 8910)]
+    // 1 km 未満の端数は切り上げる
+    #[case(560.1, 9130)]
+    #[case(644.0, 10010)]
+    #[case(680.0, 10010)]
+    #[case(681.0, 10340)]
+    #[case(0.5, 150)]
+    #[case(17.2, 330)]
+    #[case(105.2, 1980)]
+    #[case(1174.9, 14080)]
+    #[case(1463.8, 16390)]
+    fn by_kilometer(#[case] kilometer: f64, #[case] exp: u64) {
+        assert_eq!(
+            TrainFare { value: Amount { value: exp } },
+            calc_train_fare_by_kilometer(&OperatingKilometer { value: kilometer }).unwrap()
+        );
+    }
+
+    #[test]
+    fn by_kilometer_out_of_bands() {
+        let act = calc_train_fare_by_kilometer(&OperatingKilometer { value: 1480.1 }).err().unwrap();
+        assert_eq!("運賃表にない営業キロです ( 1480.1 km )", act.to_string());
+    }
+
+    #[rstest]
+    // 特定都区市内の駅へ乗り継ぐ場合は中心駅からの営業キロ
+    #[case(Some(Shinagawa), None, Tokyo, ShinOsaka, 8910)]
+    #[case(Some(Shinagawa), None, Tokyo, Himeji, 10010)]
+    // 在来線の区間を加える
+    #[case(None, Some(Kakogawa), Tokyo, Himeji, 10010)]
+    #[case(Some(Kakogawa), None, Himeji, Tokyo, 10010)]
+    // 地方交通線は換算キロを加える
+    #[case(None, Some(HonTatsuno), Tokyo, Himeji, 10010)]
+    #[case(None, Some(Kakogawa), ShinOsaka, Himeji, 1980)]
+    #[case(None, Some(HonTatsuno), ShinOsaka, Himeji, 1980)]
+    #[case(Some(Shinagawa), None, Tokyo, Hakata, 14080)]
+    #[case(Some(Kakogawa), None, Himeji, Hakata, 8910)]
+    fn with_connections(
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] arrival_connection: Option<ConventionalStation>,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] exp: u64,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection, arrival_connection };
+        assert_eq!(
+            (TrainFare { value: Amount { value: exp } }, TrainFareType::Ticket),
+            calc_train_fare_by_medium(&ride_section, &IcCard, &[]).unwrap()
+        );
+    }
+
+    // JR 九州の区間を含む乗り継ぎは運賃を算出しない
+    #[rstest]
+    #[case(Some(Shinagawa), None, Tokyo, KagoshimaChuo)]
+    #[case(Some(Kakogawa), None, Himeji, KagoshimaChuo)]
+    fn with_connections_to_kyushu(
+        #[case] departure_connection: Option<ConventionalStation>,
+        #[case] arrival_connection: Option<ConventionalStation>,
+        #[case] departure: Station,
+        #[case] arrival: Station,
+    ) {
+        let ride_section = RideSection { departure, arrival, departure_connection, arrival_connection };
+        let act = calc_train_fare_by_medium(&ride_section, &Ticket, &[]).err().unwrap();
+        assert_eq!("JR九州の区間を含む在来線の乗り継ぎは運賃を算出できません", act.to_string());
+    }
+
+    #[rstest]
+    #[case(TrainFareType::Ticket, 8910, 4450)]
+    #[case(TrainFareType::Ic, 8905, 4452)]
+    fn child_rounding(#[case] train_fare_type: TrainFareType, #[case] adult_fare: u64, #[case] exp: u64) {
+        assert_eq!(exp, train_fare_type.get_rounding().round(&Fraction::new(adult_fare, 2)));
     }
 }
@@ -0,0 +1,163 @@
+use anyhow::{anyhow, bail, Context};
+use chrono::NaiveDate;
+
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::base::train_run::TrainRun;
+use crate::domain::inventory::seat_inventory::{Seat, SeatInventory, SeatRecord, SeatState};
+
+const HEADER: &str = "# train,number,date,car,seat,seat_type,state";
+
+// 1 行 1 座席のカンマ区切りで、空行と # で始まる行は読み飛ばす
+// train,number,date,car,seat,seat_type,state
+// 列車は最初に現れた順に並べ、座席は列車ごとに現れた順に並べる
+pub fn parse_seat_inventories(text: &str) -> anyhow::Result<Vec<SeatInventory>> {
+    let mut inventories: Vec<SeatInventory> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (train_run, record) = parse_seat_row(line).with_context(|| format!("{line_number} 行目"))?;
+        match inventories.iter_mut().find(|inventory| inventory.train_run == train_run) {
+            Some(inventory) => inventory.seats.push(record),
+            None => inventories.push(SeatInventory { train_run, seats: vec![record] }),
+        }
+    }
+
+    Ok(inventories)
+}
+
+pub fn format_seat_inventories(inventories: &[SeatInventory]) -> String {
+    let mut lines = vec![HEADER.to_string()];
+    for inventory in inventories {
+        let train_run = &inventory.train_run;
+        for record in &inventory.seats {
+            lines.push(format!(
+                "{},{},{},{},{}{},{},{}",
+                train_run.train.format(),
+                train_run.number,
+                train_run.departure_date.value.format("%Y-%m-%d"),
+                record.seat.car,
+                record.seat.row,
+                record.seat.column,
+                format_seat_type(&record.seat_type),
+                format_seat_state(&record.state),
+            ));
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+fn parse_seat_row(line: &str) -> anyhow::Result<(TrainRun, SeatRecord)> {
+    let columns: Vec<&str> = line.split(',').map(|column| column.trim()).collect();
+    if columns.len() != 7 {
+        bail!("列数が 7 ではありません ( {} 列 )", columns.len());
+    }
+
+    let train_run = TrainRun {
+        train: Train::parse(columns[0])?,
+        number: columns[1].parse().with_context(|| format!("列車番号が不正です ( {} )", columns[1]))?,
+        departure_date: DepartureDate {
+            value: NaiveDate::parse_from_str(columns[2], "%Y-%m-%d")
+                .with_context(|| format!("乗車日が不正です ( {} )", columns[2]))?,
+        },
+    };
+    let record = SeatRecord {
+        seat: Seat {
+            car: columns[3].parse().with_context(|| format!("号車が不正です ( {} )", columns[3]))?,
+            ..parse_seat(columns[4])?
+        },
+        seat_type: parse_seat_type(columns[5])?,
+        state: parse_seat_state(columns[6])?,
+    };
+    Ok((train_run, record))
+}
+
+// 号車は別の列で与えるため 0 とする
+fn parse_seat(value: &str) -> anyhow::Result<Seat> {
+    let error = || anyhow!("座席番号が不正です ( {value} )");
+    let column = value.chars().last().filter(char::is_ascii_uppercase).ok_or_else(error)?;
+    let row = value[..value.len() - 1].parse().map_err(|_| error())?;
+    Ok(Seat { car: 0, row, column })
+}
+
+fn parse_seat_type(value: &str) -> anyhow::Result<SeatType> {
+    match value {
+        "Reserved" => Ok(SeatType::Reserved),
+        "Free" => Ok(SeatType::Free),
+        _ => Err(anyhow!("未知の座席種別です ( {value} )")),
+    }
+}
+
+fn format_seat_type(seat_type: &SeatType) -> &'static str {
+    match seat_type {
+        SeatType::Reserved => "Reserved",
+        SeatType::Free => "Free",
+    }
+}
+
+fn parse_seat_state(value: &str) -> anyhow::Result<SeatState> {
+    match value {
+        "Available" => Ok(SeatState::Available),
+        "Held" => Ok(SeatState::Held),
+        "Confirmed" => Ok(SeatState::Confirmed),
+        _ => Err(anyhow!("未知の座席状態です ( {value} )")),
+    }
+}
+
+fn format_seat_state(state: &SeatState) -> &'static str {
+    match state {
+        SeatState::Available => "Available",
+        SeatState::Held => "Held",
+        SeatState::Confirmed => "Confirmed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::inventory::inventory_store::{format_seat_inventories, parse_seat_inventories};
+    use crate::domain::inventory::seat_inventory::Seat;
+    use crate::domain::inventory::seat_inventory::SeatState::*;
+
+    const TEXT: &str = "# train,number,date,car,seat,seat_type,state
+Nozomi,1,2024-12-28,1,1A,Free,Available
+Nozomi,1,2024-12-28,4,12E,Reserved,Confirmed
+Nozomi,3,2024-12-28,4,1A,Reserved,Held
+";
+
+    #[test]
+    fn parse() {
+        let act = parse_seat_inventories(TEXT).unwrap();
+        assert_eq!(vec![1, 3], act.iter().map(|inventory| inventory.train_run.number).collect::<Vec<_>>());
+        assert_eq!(2, act[0].seats.len());
+        assert_eq!(Seat { car: 4, row: 12, column: 'E' }, act[0].seats[1].seat);
+        assert_eq!(Reserved, act[0].seats[1].seat_type);
+        assert_eq!(Confirmed, act[0].seats[1].state);
+        assert_eq!(Held, act[1].seats[0].state);
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(TEXT, format_seat_inventories(&parse_seat_inventories(TEXT).unwrap()));
+    }
+
+    #[rstest]
+    #[case("Nozomi,1,2024-12-28,4,1A,Reserved", "1 行目")]
+    #[case("Kodama,1,2024-12-28,4,1A,Reserved,Available", "未知の列車です ( Kodama )")]
+    #[case("Nozomi,1,2024-12-32,4,1A,Reserved,Available", "乗車日が不正です ( 2024-12-32 )")]
+    #[case("Nozomi,1,2024-12-28,4,A,Reserved,Available", "座席番号が不正です ( A )")]
+    #[case("Nozomi,1,2024-12-28,4,1a,Reserved,Available", "座席番号が不正です ( 1a )")]
+    #[case("Nozomi,1,2024-12-28,4,1A,Green,Available", "未知の座席種別です ( Green )")]
+    #[case("Nozomi,1,2024-12-28,4,1A,Reserved,Sold", "未知の座席状態です ( Sold )")]
+    fn parse_error(#[case] text: &str, #[case] exp: &str) {
+        let error = parse_seat_inventories(text).unwrap_err();
+        assert!(format!("{error:#}").contains(exp));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod inventory_store;
+pub mod seat_allocation;
+pub mod seat_inventory;
@@ -0,0 +1,94 @@
+use crate::domain::base::train_run::TrainRun;
+use crate::domain::inventory::seat_inventory::SeatInventory;
+
+// 列車 1 本に割り当てる指定席の人数
+#[derive(PartialEq, Debug)]
+pub struct SeatAllocation {
+    pub train_run: TrainRun,
+    pub count: usize,
+}
+
+// 1 本の列車に全員が乗れる場合は最初に見つかった列車にまとめる
+// 乗れない場合は列車の順に空席を埋めて複数の列車に分け、それでも足りなければ None を返す
+pub fn calc_seat_allocation(inventories: &[SeatInventory], count: usize) -> Option<Vec<SeatAllocation>> {
+    if let Some(inventory) = inventories.iter().find(|inventory| count <= inventory.get_available_count()) {
+        return Some(vec![SeatAllocation { train_run: inventory.train_run, count }]);
+    }
+
+    let mut allocations = vec![];
+    let mut rest = count;
+    for inventory in inventories {
+        if rest == 0 {
+            break;
+        }
+        let allocated = rest.min(inventory.get_available_count());
+        if 0 < allocated {
+            allocations.push(SeatAllocation { train_run: inventory.train_run, count: allocated });
+            rest -= allocated;
+        }
+    }
+
+    if rest == 0 {
+        Some(allocations)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::seat_type::SeatType::Reserved;
+    use crate::domain::base::train::Train::Nozomi;
+    use crate::domain::base::train_run::TrainRun;
+    use crate::domain::inventory::seat_allocation::calc_seat_allocation;
+    use crate::domain::inventory::seat_inventory::SeatState::Available;
+    use crate::domain::inventory::seat_inventory::{Seat, SeatInventory, SeatRecord};
+
+    fn train_run(number: u32) -> TrainRun {
+        TrainRun {
+            train: Nozomi,
+            number,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() },
+        }
+    }
+
+    fn inventory(number: u32, available: u32) -> SeatInventory {
+        SeatInventory {
+            train_run: train_run(number),
+            seats: (1..=available)
+                .map(|row| SeatRecord {
+                    seat: Seat { car: 4, row, column: 'A' },
+                    seat_type: Reserved,
+                    state: Available,
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    // 1 本にまとめる
+    #[case(vec![(1, 40), (3, 40)], 30, Some(vec![(1, 30)]))]
+    // 最初の列車に乗れなければ次の列車にまとめる
+    #[case(vec![(1, 20), (3, 60)], 60, Some(vec![(3, 60)]))]
+    // 分割する
+    #[case(vec![(1, 40), (3, 30)], 60, Some(vec![(1, 40), (3, 20)]))]
+    #[case(vec![(1, 0), (3, 30), (5, 30)], 60, Some(vec![(3, 30), (5, 30)]))]
+    // 足りない
+    #[case(vec![(1, 40), (3, 19)], 60, None)]
+    fn test_calc_seat_allocation(
+        #[case] inventories: Vec<(u32, u32)>,
+        #[case] count: usize,
+        #[case] exp: Option<Vec<(u32, usize)>>,
+    ) {
+        let inventories =
+            inventories.into_iter().map(|(number, available)| inventory(number, available)).collect::<Vec<_>>();
+        let act = calc_seat_allocation(&inventories, count).map(|allocations| {
+            allocations.iter().map(|allocation| (allocation.train_run.number, allocation.count)).collect::<Vec<_>>()
+        });
+        assert_eq!(exp, act);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train_run::TrainRun;
+
+// 号車と座席番号 ( 1A など ) で座席を特定する
+// 号車、列番号、座席記号の順に並べる
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Debug)]
+pub struct Seat {
+    pub car: u32,
+    pub row: u32,
+    pub column: char,
+}
+
+impl Seat {
+    pub fn describe(&self) -> String {
+        format!("{} 号車 {}{}", self.car, self.row, self.column)
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum SeatState {
+    Available,
+    // 確保したが発券前の状態
+    Held,
+    Confirmed,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct SeatRecord {
+    pub seat: Seat,
+    pub seat_type: SeatType,
+    pub state: SeatState,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum InventoryError {
+    NotEnoughSeats { requested: usize, available: usize },
+    NotHeld(Seat),
+    NotReserved(Seat),
+    UnknownSeat(Seat),
+}
+
+impl InventoryError {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NotEnoughSeats { requested, available } => {
+                format!("指定席が足りません ( 必要 {requested} 席 / 空席 {available} 席 )")
+            }
+            Self::NotHeld(seat) => format!("{} は確保されていません", seat.describe()),
+            Self::NotReserved(seat) => format!("{} は予約されていません", seat.describe()),
+            Self::UnknownSeat(seat) => format!("{} は存在しません", seat.describe()),
+        }
+    }
+}
+
+// 列車 1 本分の座席と予約状況
+// 自由席は座席を指定しないため、定員の算出にだけ用いる
+#[derive(PartialEq, Clone, Debug)]
+pub struct SeatInventory {
+    pub train_run: TrainRun,
+    pub seats: Vec<SeatRecord>,
+}
+
+impl SeatInventory {
+    pub fn get_capacity(&self, seat_type: &SeatType) -> usize {
+        self.seats.iter().filter(|record| record.seat_type == *seat_type).count()
+    }
+
+    pub fn get_available_count(&self) -> usize {
+        self.seats.iter().filter(|record| is_available_reserved_seat(record)).count()
+    }
+
+    // 空いている指定席を号車と座席番号の順に確保する ( 在庫の並び順にはよらない )
+    // 足りない場合は 1 席も確保しない
+    pub fn hold(&mut self, count: usize) -> Result<Vec<Seat>, InventoryError> {
+        let available = self.get_available_count();
+        if available < count {
+            return Err(InventoryError::NotEnoughSeats { requested: count, available });
+        }
+
+        let mut indexes =
+            (0..self.seats.len()).filter(|index| is_available_reserved_seat(&self.seats[*index])).collect::<Vec<_>>();
+        indexes.sort_by_key(|index| self.seats[*index].seat);
+
+        let mut seats = vec![];
+        for index in indexes.into_iter().take(count) {
+            self.seats[index].state = SeatState::Held;
+            seats.push(self.seats[index].seat);
+        }
+        Ok(seats)
+    }
+
+    // 確保した座席を予約済みにする
+    // 確保されていない座席が含まれる場合は 1 席も変更しない
+    pub fn confirm(&mut self, seats: &[Seat]) -> Result<(), InventoryError> {
+        self.transition(seats, |state| state == SeatState::Held, InventoryError::NotHeld, SeatState::Confirmed)
+    }
+
+    // 確保または予約した座席を空席に戻す
+    pub fn release(&mut self, seats: &[Seat]) -> Result<(), InventoryError> {
+        self.transition(seats, |state| state != SeatState::Available, InventoryError::NotReserved, SeatState::Available)
+    }
+
+    fn transition(
+        &mut self,
+        seats: &[Seat],
+        is_allowed: fn(SeatState) -> bool,
+        error: fn(Seat) -> InventoryError,
+        next_state: SeatState,
+    ) -> Result<(), InventoryError> {
+        let mut indexes = vec![];
+        for seat in seats {
+            let index =
+                self.seats.iter().position(|record| record.seat == *seat).ok_or(InventoryError::UnknownSeat(*seat))?;
+            if !is_allowed(self.seats[index].state) {
+                return Err(error(*seat));
+            }
+            indexes.push(index);
+        }
+
+        for index in indexes {
+            self.seats[index].state = next_state;
+        }
+        Ok(())
+    }
+}
+
+fn is_available_reserved_seat(record: &SeatRecord) -> bool {
+    record.seat_type == SeatType::Reserved && record.state == SeatState::Available
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train::Nozomi;
+    use crate::domain::base::train_run::TrainRun;
+    use crate::domain::inventory::seat_inventory::SeatState::*;
+    use crate::domain::inventory::seat_inventory::{InventoryError, Seat, SeatInventory, SeatRecord};
+
+    // 1 号車が自由席 2 席、 2 号車が指定席 3 席
+    fn inventory() -> SeatInventory {
+        let record =
+            |car, column, seat_type| SeatRecord { seat: Seat { car, row: 1, column }, seat_type, state: Available };
+        SeatInventory {
+            train_run: TrainRun {
+                train: Nozomi,
+                number: 1,
+                departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() },
+            },
+            seats: vec![
+                record(1, 'A', Free),
+                record(1, 'B', Free),
+                record(2, 'A', Reserved),
+                record(2, 'B', Reserved),
+                record(2, 'C', Reserved),
+            ],
+        }
+    }
+
+    fn seat(column: char) -> Seat {
+        Seat { car: 2, row: 1, column }
+    }
+
+    #[test]
+    fn capacity() {
+        let sut = inventory();
+        assert_eq!(2, sut.get_capacity(&Free));
+        assert_eq!(3, sut.get_capacity(&Reserved));
+        assert_eq!(3, sut.get_available_count());
+    }
+
+    #[test]
+    fn hold_confirm_release() {
+        let mut sut = inventory();
+
+        let held = sut.hold(2).unwrap();
+        assert_eq!(vec![seat('A'), seat('B')], held);
+        assert_eq!(1, sut.get_available_count());
+
+        assert_eq!(Ok(()), sut.confirm(&held));
+        assert_eq!(Confirmed, sut.seats[2].state);
+        assert_eq!(Err(InventoryError::NotHeld(seat('A'))), sut.confirm(&held));
+
+        assert_eq!(Ok(()), sut.release(&[seat('A')]));
+        assert_eq!(2, sut.get_available_count());
+        assert_eq!(Err(InventoryError::NotReserved(seat('A'))), sut.release(&[seat('A')]));
+    }
+
+    // 在庫の並び順によらず号車と座席番号の順に確保する
+    #[test]
+    fn hold_in_seat_order() {
+        let mut sut = inventory();
+        sut.seats.reverse();
+        sut.seats.push(SeatRecord {
+            seat: Seat { car: 2, row: 2, column: 'A' },
+            seat_type: Reserved,
+            state: Available,
+        });
+        assert_eq!(vec![seat('A'), seat('B')], sut.hold(2).unwrap());
+        assert_eq!(vec![seat('C'), Seat { car: 2, row: 2, column: 'A' }], sut.hold(2).unwrap());
+    }
+
+    #[test]
+    fn hold_not_enough_seats() {
+        let mut sut = inventory();
+        assert_eq!(Err(InventoryError::NotEnoughSeats { requested: 4, available: 3 }), sut.hold(4));
+        assert_eq!(3, sut.get_available_count());
+    }
+
+    #[test]
+    fn confirm_is_all_or_nothing() {
+        let mut sut = inventory();
+        let held = sut.hold(1).unwrap();
+        assert_eq!(Err(InventoryError::NotHeld(seat('B'))), sut.confirm(&[held[0], seat('B')]));
+        assert_eq!(Held, sut.seats[2].state);
+        assert_eq!(
+            Err(InventoryError::UnknownSeat(Seat { car: 9, row: 1, column: 'A' })),
+            sut.confirm(&[Seat { car: 9, row: 1, column: 'A' }])
+        );
+    }
+
+    #[test]
+    fn describe() {
+        assert_eq!(
+            "指定席が足りません ( 必要 4 席 / 空席 3 席 )",
+            InventoryError::NotEnoughSeats { requested: 4, available: 3 }.describe()
+        );
+        assert_eq!("2 号車 1A は確保されていません", InventoryError::NotHeld(seat('A')).describe());
+    }
+}
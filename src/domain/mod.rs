@@ -1,3 +1,9 @@
 pub mod base;
+pub mod change;
 pub mod discount;
 pub mod fare;
+pub mod inventory;
+pub mod refund;
+pub mod schedule;
+pub mod tariff;
+pub mod ticket;
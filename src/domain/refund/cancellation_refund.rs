@@ -0,0 +1,223 @@
+use std::cmp::min;
+
+use crate::domain::base::cancellation_date::CancellationDate;
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ex_product::ExProduct;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::fundamental::amount::Amount;
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
+// 発券済みの 1 人分の片道料金
+pub struct IssuedFare {
+    pub train_fare: TrainFare,
+    pub express_fare: ExpressFare,
+    pub seat_type: SeatType,
+    // EX サービスで予約した場合の商品
+    pub ex_product: Option<ExProduct>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Refund {
+    pub handling_fee: Amount,
+    pub refundable_amount: Amount,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct GroupRefund {
+    pub passengers: Vec<Refund>,
+    pub total: Refund,
+}
+
+// EX サービスの払戻手数料は座席種別や払い戻す日によらず 1 人 320 円
+const EX_HANDLING_FEE: Amount = Amount { value: 320 };
+// 指定席特急料金の 30 % の払戻手数料は 10 円未満を切り捨てる
+const CANCELLATION_FEE_ROUNDING: Rounding = Rounding::FLOOR_TO_TEN;
+
+// 出発日を過ぎた場合は払い戻せないので None を返す
+// 出発時刻は扱わないため、出発日当日は出発前として扱う
+pub fn calc_refund(
+    issued_fare: &IssuedFare,
+    departure_date: &DepartureDate,
+    cancellation_date: &CancellationDate,
+) -> Option<Refund> {
+    let days_before = cancellation_date.get_days_before(departure_date);
+    if days_before < 0 {
+        return None;
+    }
+
+    let paid = issued_fare.train_fare.value.clone() + issued_fare.express_fare.value.clone();
+    if issued_fare.ex_product.is_some() {
+        let handling_fee = min_amount(EX_HANDLING_FEE, &paid);
+        return Some(Refund { refundable_amount: paid - handling_fee.clone(), handling_fee });
+    }
+
+    let train_fare_fee = Amount { value: 220 };
+    let express_fare_fee = match issued_fare.seat_type {
+        SeatType::Reserved if 2 <= days_before => Amount { value: 340 },
+        SeatType::Reserved => {
+            let fee = issued_fare.express_fare.value.multiply(&Fraction::percent(30), &CANCELLATION_FEE_ROUNDING);
+            if fee.value < 340 {
+                Amount { value: 340 }
+            } else {
+                fee
+            }
+        }
+        SeatType::Free => Amount { value: 220 },
+    };
+
+    // 手数料は券面額を超えて収受しない
+    let train_fare_fee = min_amount(train_fare_fee, &issued_fare.train_fare.value);
+    let express_fare_fee = min_amount(express_fare_fee, &issued_fare.express_fare.value);
+
+    let handling_fee = train_fare_fee + express_fare_fee;
+    Some(Refund { refundable_amount: paid - handling_fee.clone(), handling_fee })
+}
+
+pub fn calc_group_refund(
+    issued_fares: &[IssuedFare],
+    departure_date: &DepartureDate,
+    cancellation_date: &CancellationDate,
+) -> Option<GroupRefund> {
+    let passengers = issued_fares
+        .iter()
+        .map(|issued_fare| calc_refund(issued_fare, departure_date, cancellation_date))
+        .collect::<Option<Vec<_>>>()?;
+
+    let total = passengers.iter().fold(
+        Refund { handling_fee: Amount { value: 0 }, refundable_amount: Amount { value: 0 } },
+        |acc, refund| Refund {
+            handling_fee: acc.handling_fee + refund.handling_fee.clone(),
+            refundable_amount: acc.refundable_amount + refund.refundable_amount.clone(),
+        },
+    );
+
+    Some(GroupRefund { passengers, total })
+}
+
+fn min_amount(fee: Amount, paid: &Amount) -> Amount {
+    Amount { value: min(fee.value, paid.value) }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::cancellation_date::CancellationDate;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ex_product::ExProduct;
+    use crate::domain::base::ex_product::ExProduct::*;
+    use crate::domain::base::seat_type::SeatType;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::fare::express_fare::ExpressFare;
+    use crate::domain::fare::train_fare::TrainFare;
+    use crate::domain::refund::cancellation_refund::{calc_group_refund, calc_refund, IssuedFare, Refund};
+    use crate::fundamental::amount::Amount;
+
+    fn issued_fare(train_fare: u64, express_fare: u64, seat_type: SeatType) -> IssuedFare {
+        IssuedFare {
+            train_fare: TrainFare { value: Amount { value: train_fare } },
+            express_fare: ExpressFare { value: Amount { value: express_fare } },
+            seat_type,
+            ex_product: None,
+        }
+    }
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+
+    #[rstest]
+    // 指定席 2 日前まで
+    #[case(10010, 6450, Reserved, 12, 1, 560, 15900)]
+    #[case(10010, 6450, Reserved, 12, 26, 560, 15900)]
+    // 指定席 前日から当日 ( 30 % )
+    #[case(10010, 6450, Reserved, 12, 27, 2150, 14310)]
+    #[case(10010, 6450, Reserved, 12, 28, 2150, 14310)]
+    // 指定席 30 % が 340 円を下回る場合
+    #[case(5000, 1000, Reserved, 12, 28, 560, 5440)]
+    // 自由席
+    #[case(10010, 5390, Free, 12, 1, 440, 14960)]
+    #[case(10010, 5390, Free, 12, 28, 440, 14960)]
+    // 手数料は券面額まで
+    #[case(0, 0, Reserved, 12, 1, 0, 0)]
+    fn test_calc_refund(
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+        #[case] seat_type: SeatType,
+        #[case] m: u32,
+        #[case] d: u32,
+        #[case] handling_fee: u64,
+        #[case] refundable_amount: u64,
+    ) {
+        let departure_date = DepartureDate { value: date(12, 28) };
+        let cancellation_date = CancellationDate { value: date(m, d) };
+        assert_eq!(
+            Some(Refund {
+                handling_fee: Amount { value: handling_fee },
+                refundable_amount: Amount { value: refundable_amount }
+            }),
+            calc_refund(&issued_fare(train_fare, express_fare, seat_type), &departure_date, &cancellation_date)
+        );
+    }
+
+    #[rstest]
+    #[case(SmartEx, 10010, 6250, 12, 1, 320, 15940)]
+    #[case(ExReservation, 10010, 5660, 12, 28, 320, 15350)]
+    // 手数料は券面額まで
+    #[case(SmartEx, 0, 200, 12, 28, 200, 0)]
+    fn test_calc_ex_refund(
+        #[case] ex_product: ExProduct,
+        #[case] train_fare: u64,
+        #[case] express_fare: u64,
+        #[case] m: u32,
+        #[case] d: u32,
+        #[case] handling_fee: u64,
+        #[case] refundable_amount: u64,
+    ) {
+        let issued_fare =
+            IssuedFare { ex_product: Some(ex_product), ..issued_fare(train_fare, express_fare, Reserved) };
+        let departure_date = DepartureDate { value: date(12, 28) };
+        let cancellation_date = CancellationDate { value: date(m, d) };
+        assert_eq!(
+            Some(Refund {
+                handling_fee: Amount { value: handling_fee },
+                refundable_amount: Amount { value: refundable_amount }
+            }),
+            calc_refund(&issued_fare, &departure_date, &cancellation_date)
+        );
+        let cancellation_date = CancellationDate { value: date(12, 29) };
+        assert_eq!(None, calc_refund(&issued_fare, &departure_date, &cancellation_date));
+    }
+
+    #[test]
+    fn test_calc_refund_after_departure() {
+        let departure_date = DepartureDate { value: date(12, 28) };
+        let cancellation_date = CancellationDate { value: date(12, 29) };
+        assert_eq!(None, calc_refund(&issued_fare(10010, 6450, Reserved), &departure_date, &cancellation_date));
+    }
+
+    #[test]
+    fn test_calc_group_refund() {
+        let departure_date = DepartureDate { value: date(12, 28) };
+        let cancellation_date = CancellationDate { value: date(12, 27) };
+        let issued_fares = vec![issued_fare(10010, 6450, Reserved), issued_fare(5000, 3220, Reserved)];
+
+        let act = calc_group_refund(&issued_fares, &departure_date, &cancellation_date).unwrap();
+
+        assert_eq!(
+            vec![
+                Refund { handling_fee: Amount { value: 2150 }, refundable_amount: Amount { value: 14310 } },
+                Refund { handling_fee: Amount { value: 1180 }, refundable_amount: Amount { value: 7040 } },
+            ],
+            act.passengers
+        );
+        assert_eq!(
+            Refund { handling_fee: Amount { value: 3330 }, refundable_amount: Amount { value: 21350 } },
+            act.total
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod timetable;
+pub mod train_search;
@@ -0,0 +1,189 @@
+use anyhow::{anyhow, bail, Context};
+use chrono::{Datelike, NaiveTime, Weekday};
+
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ride_section::Station;
+use crate::domain::base::train::Train;
+use crate::domain::base::train_run::TrainRun;
+
+// 停車駅と発車時刻 ( 終着駅は到着時刻 )
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Stop {
+    pub station: Station,
+    pub time: NaiveTime,
+}
+
+// 時刻表の 1 列車分
+// stops は停車する順に並べる
+#[derive(PartialEq, Debug)]
+pub struct TimetableEntry {
+    pub train: Train,
+    pub number: u32,
+    pub operating_days: Vec<Weekday>,
+    pub stops: Vec<Stop>,
+}
+
+impl TimetableEntry {
+    pub fn operates_on(&self, departure_date: &DepartureDate) -> bool {
+        self.operating_days.contains(&departure_date.value.weekday())
+    }
+
+    // 発駅から着駅へこの順に停車する場合に発駅と着駅の停車を返す
+    pub fn get_stops_between(&self, departure: &Station, arrival: &Station) -> Option<(&Stop, &Stop)> {
+        let departure_index = self.stops.iter().position(|stop| stop.station == *departure)?;
+        let arrival_index = self.stops.iter().position(|stop| stop.station == *arrival)?;
+        if departure_index < arrival_index {
+            Some((&self.stops[departure_index], &self.stops[arrival_index]))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_train_run(&self, departure_date: &DepartureDate) -> TrainRun {
+        TrainRun { train: self.train, number: self.number, departure_date: *departure_date }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Timetable {
+    pub entries: Vec<TimetableEntry>,
+}
+
+// 1 行 1 列車のカンマ区切りで、空行と # で始まる行は読み飛ばす
+// train,number,operating_days,stops
+// operating_days は Daily または空白区切りの曜日 ( Sat Sun など )
+// stops は ; 区切りの 駅 時刻 ( Tokyo 09:00;Himeji 11:57 など )
+pub fn parse_timetable(text: &str) -> anyhow::Result<Timetable> {
+    let mut entries = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = parse_timetable_entry(line).with_context(|| format!("{line_number} 行目"))?;
+        entries.push(entry);
+    }
+
+    Ok(Timetable { entries })
+}
+
+fn parse_timetable_entry(line: &str) -> anyhow::Result<TimetableEntry> {
+    let columns: Vec<&str> = line.split(',').map(|column| column.trim()).collect();
+    if columns.len() != 4 {
+        bail!("列数が 4 ではありません ( {} 列 )", columns.len());
+    }
+
+    let stops = columns[3].split(';').map(|stop| parse_stop(stop.trim())).collect::<anyhow::Result<Vec<_>>>()?;
+    if stops.len() < 2 {
+        bail!("停車駅が 2 駅未満です");
+    }
+    if stops.windows(2).any(|pair| pair[1].time < pair[0].time) {
+        bail!("時刻が停車順に並んでいません");
+    }
+
+    Ok(TimetableEntry {
+        train: Train::parse(columns[0])?,
+        number: columns[1].parse().with_context(|| format!("列車番号が不正です ( {} )", columns[1]))?,
+        operating_days: parse_operating_days(columns[2])?,
+        stops,
+    })
+}
+
+fn parse_stop(value: &str) -> anyhow::Result<Stop> {
+    let (station, time) = value.split_once(' ').ok_or_else(|| anyhow!("停車駅が不正です ( {value} )"))?;
+    Ok(Stop {
+        station: Station::parse(station)?,
+        time: NaiveTime::parse_from_str(time.trim(), "%H:%M").with_context(|| format!("時刻が不正です ( {time} )"))?,
+    })
+}
+
+fn parse_operating_days(value: &str) -> anyhow::Result<Vec<Weekday>> {
+    if value == "Daily" {
+        return Ok(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+    }
+    value
+        .split_whitespace()
+        .map(|day| day.parse::<Weekday>().map_err(|_| anyhow!("曜日が不正です ( {day} )")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime, Weekday};
+    use rstest::rstest;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::schedule::timetable::{parse_timetable, Stop};
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let text = "# comment\n\nNozomi, 21, Daily, Tokyo 09:00;ShinOsaka 11:27;Himeji 11:57\nHikari,639,Sat Sun,Tokyo 08:33;ShinOsaka 11:24\n";
+        let act = parse_timetable(text).unwrap();
+        assert_eq!(2, act.entries.len());
+        assert_eq!(Nozomi, act.entries[0].train);
+        assert_eq!(21, act.entries[0].number);
+        assert_eq!(7, act.entries[0].operating_days.len());
+        assert_eq!(
+            vec![
+                Stop { station: Tokyo, time: time(9, 0) },
+                Stop { station: ShinOsaka, time: time(11, 27) },
+                Stop { station: Himeji, time: time(11, 57) },
+            ],
+            act.entries[0].stops
+        );
+        assert_eq!(vec![Weekday::Sat, Weekday::Sun], act.entries[1].operating_days);
+    }
+
+    #[rstest]
+    // 2024 年 12 月 28 日は土曜日、 12 月 30 日は月曜日
+    #[case("Daily", 28, true)]
+    #[case("Sat Sun", 28, true)]
+    #[case("Sat Sun", 30, false)]
+    fn operates_on(#[case] operating_days: &str, #[case] d: u32, #[case] exp: bool) {
+        let text = format!("Nozomi,21,{operating_days},Tokyo 09:00;Himeji 11:57");
+        let sut = &parse_timetable(&text).unwrap().entries[0];
+        assert_eq!(exp, sut.operates_on(&DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, d).unwrap() }));
+    }
+
+    #[test]
+    fn get_stops_between() {
+        let timetable = parse_timetable("Nozomi,21,Daily,Tokyo 09:00;ShinOsaka 11:27").unwrap();
+        let sut = &timetable.entries[0];
+        assert_eq!(
+            Some((&Stop { station: Tokyo, time: time(9, 0) }, &Stop { station: ShinOsaka, time: time(11, 27) })),
+            sut.get_stops_between(&Tokyo, &ShinOsaka)
+        );
+        assert_eq!(None, sut.get_stops_between(&ShinOsaka, &Tokyo));
+        assert_eq!(None, sut.get_stops_between(&Tokyo, &Himeji));
+    }
+
+    #[rstest]
+    #[case("Nozomi,21,Daily", "1 行目")]
+    #[case("Kodama,21,Daily,Tokyo 09:00;Himeji 11:57", "未知の列車です ( Kodama )")]
+    #[case("Nozomi,21,Holiday,Tokyo 09:00;Himeji 11:57", "曜日が不正です ( Holiday )")]
+    #[case("Nozomi,21,Daily,Tokyo 09:00", "停車駅が 2 駅未満です")]
+    #[case("Nozomi,21,Daily,Tokyo 09:00;Nagoya 10:35", "未知の駅です ( Nagoya )")]
+    #[case("Nozomi,21,Daily,Tokyo 9時;Himeji 11:57", "時刻が不正です ( 9時 )")]
+    #[case("Nozomi,21,Daily,Tokyo;Himeji 11:57", "停車駅が不正です ( Tokyo )")]
+    #[case("Nozomi,21,Daily,Tokyo 12:00;Himeji 11:57", "時刻が停車順に並んでいません")]
+    fn parse_error(#[case] text: &str, #[case] exp: &str) {
+        let error = parse_timetable(text).unwrap_err();
+        assert!(format!("{error:#}").contains(exp));
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveTime;
+
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::base::train_run::TrainRun;
+use crate::domain::schedule::timetable::Timetable;
+
+// 乗車区間を走る列車 1 本と発駅の発車時刻、着駅の到着時刻
+#[derive(PartialEq, Debug)]
+pub struct ScheduledRun {
+    pub train_run: TrainRun,
+    pub departure_time: NaiveTime,
+    pub arrival_time: NaiveTime,
+}
+
+// 乗車日に運転され、発駅を指定時刻以降に発車して着駅にも停車する列車を発車時刻順に返す
+pub fn search_train_runs(
+    timetable: &Timetable,
+    ride_section: &RideSection,
+    departure_date: &DepartureDate,
+    earliest_departure_time: &NaiveTime,
+) -> Vec<ScheduledRun> {
+    let mut runs = timetable
+        .entries
+        .iter()
+        .filter(|entry| entry.operates_on(departure_date))
+        .filter_map(|entry| {
+            let (departure, arrival) = entry.get_stops_between(&ride_section.departure, &ride_section.arrival)?;
+            Some(ScheduledRun {
+                train_run: entry.get_train_run(departure_date),
+                departure_time: departure.time,
+                arrival_time: arrival.time,
+            })
+        })
+        .filter(|run| *earliest_departure_time <= run.departure_time)
+        .collect::<Vec<_>>();
+    runs.sort_by_key(|run| run.departure_time);
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+    use rstest::rstest;
+
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::ride_section::{RideSection, Station};
+    use crate::domain::schedule::timetable::parse_timetable;
+    use crate::domain::schedule::train_search::search_train_runs;
+
+    const TEXT: &str = "
+Nozomi,21,Daily,Tokyo 09:00;ShinOsaka 11:27;Himeji 11:57
+Nozomi,15,Daily,Tokyo 08:09;ShinOsaka 10:36
+Nozomi,13,Daily,Tokyo 08:00;ShinOsaka 10:30;Himeji 11:00
+Nozomi,1,Daily,Tokyo 06:00;ShinOsaka 08:27;Himeji 08:58
+Hikari,639,Sat Sun,Tokyo 08:33;ShinOsaka 11:24;Himeji 11:59
+";
+
+    // 2024 年 12 月 28 日は土曜日、 12 月 30 日は月曜日
+    #[rstest]
+    #[case(Tokyo, Himeji, 28, 8, 0, vec![13, 639, 21])]
+    #[case(Tokyo, Himeji, 30, 8, 0, vec![13, 21])]
+    #[case(Tokyo, ShinOsaka, 30, 8, 1, vec![15, 21])]
+    #[case(Tokyo, Himeji, 30, 5, 0, vec![1, 13, 21])]
+    #[case(Himeji, Tokyo, 30, 0, 0, vec![])]
+    #[case(Tokyo, Himeji, 30, 12, 0, vec![])]
+    fn test_search_train_runs(
+        #[case] departure: Station,
+        #[case] arrival: Station,
+        #[case] d: u32,
+        #[case] h: u32,
+        #[case] m: u32,
+        #[case] exp: Vec<u32>,
+    ) {
+        let timetable = parse_timetable(TEXT).unwrap();
+        let act = search_train_runs(
+            &timetable,
+            &RideSection { departure, arrival, departure_connection: None, arrival_connection: None },
+            &DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, d).unwrap() },
+            &NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+        );
+        assert_eq!(exp, act.iter().map(|run| run.train_run.number).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn times() {
+        let timetable = parse_timetable(TEXT).unwrap();
+        let act = search_train_runs(
+            &timetable,
+            &RideSection {
+                departure: ShinOsaka,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            &DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 30).unwrap() },
+            &NaiveTime::from_hms_opt(11, 0, 0).unwrap(),
+        );
+        assert_eq!(1, act.len());
+        assert_eq!(NaiveTime::from_hms_opt(11, 27, 0).unwrap(), act[0].departure_time);
+        assert_eq!(NaiveTime::from_hms_opt(11, 57, 0).unwrap(), act[0].arrival_time);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod tariff_table;
+pub mod tariff_validation;
@@ -0,0 +1,117 @@
+use anyhow::{bail, Context};
+
+use crate::domain::base::ride_section::Station;
+use crate::fundamental::amount::Amount;
+use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+// 料金表の 1 行 ( 駅の組み合わせごとの運賃と特急料金 )
+// 検証結果で行番号を示せるように元ファイルの行番号を保持する
+#[derive(PartialEq, Debug)]
+pub struct TariffRow {
+    pub line_number: usize,
+    pub departure: Station,
+    pub arrival: Station,
+    pub operating_kilometer: OperatingKilometer,
+    pub train_fare: Amount,
+    pub reserved_hikari_express_fare: Amount,
+    pub reserved_nozomi_express_fare: Amount,
+    pub free_express_fare: Amount,
+    pub round_trip_discount: bool,
+}
+
+impl TariffRow {
+    pub fn get_station_pair(&self) -> (Station, Station) {
+        if self.departure <= self.arrival {
+            (self.departure, self.arrival)
+        } else {
+            (self.arrival, self.departure)
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TariffTable {
+    pub rows: Vec<TariffRow>,
+}
+
+// 1 行 1 区間のカンマ区切りで、空行と # で始まる行は読み飛ばす
+// departure,arrival,operating_kilometer,train_fare,reserved_hikari,reserved_nozomi,free,round_trip_discount
+pub fn parse_tariff_table(text: &str) -> anyhow::Result<TariffTable> {
+    let mut rows = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let row = parse_tariff_row(line_number, line).with_context(|| format!("{line_number} 行目"))?;
+        rows.push(row);
+    }
+
+    Ok(TariffTable { rows })
+}
+
+fn parse_tariff_row(line_number: usize, line: &str) -> anyhow::Result<TariffRow> {
+    let columns: Vec<&str> = line.split(',').map(|column| column.trim()).collect();
+    if columns.len() != 8 {
+        bail!("列数が 8 ではありません ( {} 列 )", columns.len());
+    }
+
+    Ok(TariffRow {
+        line_number,
+        departure: Station::parse(columns[0])?,
+        arrival: Station::parse(columns[1])?,
+        operating_kilometer: OperatingKilometer {
+            value: columns[2].parse().with_context(|| format!("営業キロが不正です ( {} )", columns[2]))?,
+        },
+        train_fare: parse_amount(columns[3])?,
+        reserved_hikari_express_fare: parse_amount(columns[4])?,
+        reserved_nozomi_express_fare: parse_amount(columns[5])?,
+        free_express_fare: parse_amount(columns[6])?,
+        round_trip_discount: columns[7]
+            .parse()
+            .with_context(|| format!("往復割引の有無が不正です ( {} )", columns[7]))?,
+    })
+}
+
+fn parse_amount(value: &str) -> anyhow::Result<Amount> {
+    Ok(Amount { value: value.parse().with_context(|| format!("金額が不正です ( {value} )"))? })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::tariff::tariff_table::{parse_tariff_table, TariffRow};
+    use crate::fundamental::amount::Amount;
+    use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+    #[test]
+    fn parse() {
+        let text = "# comment\n\nTokyo, Himeji, 644.0, 10010, 5920, 6450, 5390, true\n";
+        let exp = TariffRow {
+            line_number: 3,
+            departure: Tokyo,
+            arrival: Himeji,
+            operating_kilometer: OperatingKilometer { value: 644.0 },
+            train_fare: Amount { value: 10010 },
+            reserved_hikari_express_fare: Amount { value: 5920 },
+            reserved_nozomi_express_fare: Amount { value: 6450 },
+            free_express_fare: Amount { value: 5390 },
+            round_trip_discount: true,
+        };
+        assert_eq!(vec![exp], parse_tariff_table(text).unwrap().rows);
+    }
+
+    #[rstest]
+    #[case("Tokyo,Himeji,644.0,10010,5920,6450,5390", "1 行目")]
+    #[case("Tokyo,Nagoya,644.0,10010,5920,6450,5390,true", "未知の駅です ( Nagoya )")]
+    #[case("Tokyo,Himeji,644.0,10010,-1,6450,5390,true", "金額が不正です ( -1 )")]
+    #[case("Tokyo,Himeji,644.0,10010,5920,6450,5390,yes", "往復割引の有無が不正です ( yes )")]
+    fn parse_error(#[case] text: &str, #[case] exp: &str) {
+        let error = parse_tariff_table(text).unwrap_err();
+        assert!(format!("{error:#}").contains(exp));
+    }
+}
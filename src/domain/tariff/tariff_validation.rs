@@ -0,0 +1,216 @@
+use itertools::Itertools;
+
+use crate::domain::base::ride_section::{get_defined_station_pairs, RideSection};
+use crate::domain::discount::round_trip_discount::ROUND_TRIP_DISCOUNT_MIN_KILOMETER;
+use crate::domain::tariff::tariff_table::{TariffRow, TariffTable};
+use crate::fundamental::amount::Amount;
+
+// 料金表の検証結果 1 件
+// 行に紐付かない指摘 ( 区間の不足など ) は line_number を持たない
+#[derive(PartialEq, Debug)]
+pub struct TariffViolation {
+    pub line_number: Option<usize>,
+    pub message: String,
+}
+
+impl TariffViolation {
+    fn at(row: &TariffRow, message: String) -> Self {
+        Self { line_number: Some(row.line_number), message }
+    }
+}
+
+pub fn validate_tariff_table(tariff_table: &TariffTable) -> Vec<TariffViolation> {
+    let mut violations = vec![];
+
+    violations.extend(validate_completeness(tariff_table));
+    for row in &tariff_table.rows {
+        violations.extend(validate_row(row));
+    }
+    violations.extend(validate_monotonicity(tariff_table));
+
+    violations.sort_by_key(|violation| violation.line_number);
+    violations
+}
+
+// ネットワークで定義されている駅の組み合わせが過不足なく 1 行ずつあること
+fn validate_completeness(tariff_table: &TariffTable) -> Vec<TariffViolation> {
+    let mut violations = vec![];
+
+    let defined_pairs = get_defined_station_pairs();
+    for pair in &defined_pairs {
+        if !tariff_table.rows.iter().any(|row| &row.get_station_pair() == pair) {
+            violations.push(TariffViolation {
+                line_number: None,
+                message: format!("{:?} - {:?} の行がありません", pair.0, pair.1),
+            });
+        }
+    }
+
+    for (index, row) in tariff_table.rows.iter().enumerate() {
+        let pair = row.get_station_pair();
+        if !defined_pairs.contains(&pair) {
+            violations.push(TariffViolation::at(
+                row,
+                format!("{:?} - {:?} はネットワークに定義されていません", pair.0, pair.1),
+            ));
+        } else if tariff_table.rows[..index].iter().any(|other| other.get_station_pair() == pair) {
+            violations.push(TariffViolation::at(row, format!("{:?} - {:?} の行が重複しています", pair.0, pair.1)));
+        }
+    }
+
+    violations
+}
+
+fn validate_row(row: &TariffRow) -> Vec<TariffViolation> {
+    let mut violations = vec![];
+
+    let pair = row.get_station_pair();
+    if get_defined_station_pairs().contains(&pair) {
+        let ride_section =
+            RideSection { departure: pair.0, arrival: pair.1, departure_connection: None, arrival_connection: None };
+        let defined = ride_section.get_operation_kilometer();
+        if &row.operating_kilometer != defined {
+            violations.push(TariffViolation::at(
+                row,
+                format!(
+                    "営業キロ ( {} ) がネットワークの定義 ( {} ) と一致しません",
+                    row.operating_kilometer.value, defined.value
+                ),
+            ));
+        }
+    }
+
+    if row.reserved_nozomi_express_fare.value < row.reserved_hikari_express_fare.value {
+        violations.push(TariffViolation::at(
+            row,
+            format!(
+                "のぞみ指定席特急料金 ( {} ) がひかり指定席特急料金 ( {} ) を下回っています",
+                row.reserved_nozomi_express_fare.value, row.reserved_hikari_express_fare.value
+            ),
+        ));
+    }
+
+    if row.reserved_hikari_express_fare.value < row.free_express_fare.value {
+        violations.push(TariffViolation::at(
+            row,
+            format!(
+                "自由席特急料金 ( {} ) が指定席特急料金 ( {} ) を上回っています",
+                row.free_express_fare.value, row.reserved_hikari_express_fare.value
+            ),
+        ));
+    }
+
+    let expected_round_trip_discount = ROUND_TRIP_DISCOUNT_MIN_KILOMETER <= row.operating_kilometer.value;
+    if row.round_trip_discount != expected_round_trip_discount {
+        violations.push(TariffViolation::at(
+            row,
+            format!(
+                "往復割引の有無 ( {} ) が営業キロ {} km の閾値 ( {} km ) と矛盾しています",
+                row.round_trip_discount, row.operating_kilometer.value, ROUND_TRIP_DISCOUNT_MIN_KILOMETER
+            ),
+        ));
+    }
+
+    violations
+}
+
+type AmountColumn = fn(&TariffRow) -> &Amount;
+
+// 営業キロが長い区間の料金が短い区間の料金を下回らないこと
+fn validate_monotonicity(tariff_table: &TariffTable) -> Vec<TariffViolation> {
+    let columns: [(&str, AmountColumn); 4] = [
+        ("運賃", |row| &row.train_fare),
+        ("ひかり指定席特急料金", |row| &row.reserved_hikari_express_fare),
+        ("のぞみ指定席特急料金", |row| &row.reserved_nozomi_express_fare),
+        ("自由席特急料金", |row| &row.free_express_fare),
+    ];
+
+    let sorted = tariff_table
+        .rows
+        .iter()
+        .sorted_by(|a, b| a.operating_kilometer.value.total_cmp(&b.operating_kilometer.value))
+        .collect_vec();
+
+    let mut violations = vec![];
+    for (shorter, longer) in sorted.iter().tuple_windows() {
+        if shorter.operating_kilometer.value == longer.operating_kilometer.value {
+            continue;
+        }
+        for (name, get) in &columns {
+            if get(longer).value < get(shorter).value {
+                violations.push(TariffViolation::at(
+                    longer,
+                    format!(
+                        "{name} ( {} ) が営業キロの短い {} 行目 ( {} ) を下回っています",
+                        get(longer).value,
+                        shorter.line_number,
+                        get(shorter).value
+                    ),
+                ));
+            }
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::tariff::tariff_table::parse_tariff_table;
+    use crate::domain::tariff::tariff_validation::{validate_tariff_table, TariffViolation};
+
+    const VALID: &str = "Tokyo,ShinOsaka,553.0,8910,5490,5810,4960,false
+Tokyo,Himeji,644.0,10010,5920,6450,5390,true
+Tokyo,Hakata,1174.9,14080,8570,9310,8040,true
+ShinOsaka,Himeji,91.0,1520,3270,3480,2740,false
+ShinOsaka,Hakata,621.9,9790,5600,6020,5070,true
+Himeji,Hakata,530.9,8580,5380,5800,4850,false
+Tokyo,KagoshimaChuo,1463.8,17380,13800,14750,12740,true
+ShinOsaka,KagoshimaChuo,910.8,13000,8220,8850,7690,true
+Himeji,KagoshimaChuo,819.8,12100,7790,8420,7260,true
+Hakata,KagoshimaChuo,288.9,5390,5230,5440,4700,false";
+
+    #[test]
+    fn valid() {
+        let tariff_table = parse_tariff_table(VALID).unwrap();
+        assert_eq!(Vec::<TariffViolation>::new(), validate_tariff_table(&tariff_table));
+    }
+
+    #[rstest]
+    // 区間の不足
+    #[case(&VALID.replace("Tokyo,Himeji,644.0,10010,5920,6450,5390,true\n", ""), None, "Tokyo - Himeji の行がありません")]
+    // 区間の重複
+    #[case(&format!("{VALID}\nHimeji,Tokyo,644.0,10010,5920,6450,5390,true"), Some(11), "重複しています")]
+    // 未定義の区間
+    #[case(&format!("{VALID}\nTokyo,Tokyo,0.0,1000,1000,1000,1000,false"), Some(11), "定義されていません")]
+    // 営業キロの不一致
+    #[case(&VALID.replace("Tokyo,ShinOsaka,553.0", "Tokyo,ShinOsaka,552.0"), Some(1), "一致しません")]
+    // のぞみ < ひかり
+    #[case(
+        &VALID.replace("13800,14750", "13800,13790"),
+        Some(7),
+        "のぞみ指定席特急料金 ( 13790 ) がひかり指定席特急料金 ( 13800 ) を下回っています"
+    )]
+    // 自由席 > 指定席
+    #[case(
+        &VALID.replace("9310,8040", "9310,8580"),
+        Some(3),
+        "自由席特急料金 ( 8580 ) が指定席特急料金 ( 8570 ) を上回っています"
+    )]
+    // 往復割引の閾値
+    #[case(&VALID.replace("4960,false", "4960,true"), Some(1), "往復割引の有無 ( true )")]
+    // 営業キロに対する単調性
+    #[case(
+        &VALID.replace("644.0,10010", "644.0,8900"),
+        Some(2),
+        "運賃 ( 8900 ) が営業キロの短い 5 行目 ( 9790 ) を下回っています"
+    )]
+    fn invalid(#[case] text: &str, #[case] line_number: Option<usize>, #[case] message: &str) {
+        let tariff_table = parse_tariff_table(text).unwrap();
+        let violations = validate_tariff_table(&tariff_table);
+        assert_eq!(1, violations.len(), "{violations:?}");
+        assert_eq!(line_number, violations[0].line_number);
+        assert!(violations[0].message.contains(message), "{}", violations[0].message);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod ticket_issuance;
+pub mod validity_period;
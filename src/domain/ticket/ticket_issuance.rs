@@ -0,0 +1,292 @@
+use crate::domain::base::city_zone::CityZone;
+use crate::domain::base::departure_date::DepartureDate;
+use crate::domain::base::fare_query::FareQuery;
+use crate::domain::base::number_of_passengers::NumberOfPassengers;
+use crate::domain::base::reserve_type::ReserveType;
+use crate::domain::base::ride_section::RideSection;
+use crate::domain::base::seat_type::SeatType;
+use crate::domain::base::train::Train;
+use crate::domain::discount::discount_policy::DiscountPolicy;
+use crate::domain::discount::individual_discount::IndividualDiscount;
+use crate::domain::discount::overall_discount::OverallDiscount;
+use crate::domain::fare::express_fare::ExpressFare;
+use crate::domain::fare::passenger_fare::{calc_adult_fare, calc_child_fare};
+use crate::domain::fare::single_trip_adult_fare::SingleTripAdultFare;
+use crate::domain::fare::train_fare::TrainFare;
+use crate::domain::ticket::validity_period::{calc_validity_period, ValidityPeriod};
+use crate::fundamental::amount::Amount;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum TicketType {
+    // 乗車券
+    TrainTicket,
+    // 特急券
+    ExpressTicket,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum PassengerCategory {
+    Adult,
+    Child,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Direction {
+    Outbound,
+    Return,
+}
+
+// 乗車券は列車と座席を指定しないため train と seat_type を持たない
+// 特急券は指定した列車に限り有効なため validity_period を持たない
+// 特定都区市内を適用した乗車券は区域内のどの駅からでも ( どの駅まででも ) 有効で、特急券は駅を指定する
+// 在来線で乗り継ぐ場合、乗車券は在来線の駅まで、特急券は新幹線の区間だけを記載する
+#[derive(PartialEq, Debug)]
+pub struct Ticket {
+    pub ticket_type: TicketType,
+    pub passenger_category: PassengerCategory,
+    pub direction: Direction,
+    pub ride_section: RideSection,
+    pub city_zones: (Option<CityZone>, Option<CityZone>),
+    pub departure_date: DepartureDate,
+    pub train: Option<Train>,
+    pub seat_type: Option<SeatType>,
+    pub validity_period: Option<ValidityPeriod>,
+    pub price: Amount,
+}
+
+// 1 人 1 方向ごとに乗車券と特急券を 1 枚ずつ発券する
+// 全体割引で無料になる旅客の券も 0 円で発券するので、券面額の合計は総料金と一致する
+// 復路の乗車日は問い合わせに含まれないため、往路と同じ出発日で発券する
+pub fn issue_tickets(
+    fare_query: &FareQuery,
+    single_trip_adult_fare: &SingleTripAdultFare,
+    overall_discount: &Option<OverallDiscount>,
+    individual_discounts: &[IndividualDiscount],
+    discount_policy: &DiscountPolicy,
+) -> Vec<Ticket> {
+    let adult_fare = calc_adult_fare(single_trip_adult_fare, individual_discounts, discount_policy);
+    let child_fare = calc_child_fare(single_trip_adult_fare, individual_discounts, discount_policy);
+    let free_fare = (TrainFare { value: Amount { value: 0 } }, ExpressFare { value: Amount { value: 0 } });
+
+    let free_passengers = match overall_discount {
+        Some(overall_discount) => overall_discount.get_free_passengers(),
+        None => NumberOfPassengers { adult: 0, child: 0 },
+    };
+    let number_of_passengers = &fare_query.number_of_passengers;
+
+    let passengers = [
+        (PassengerCategory::Adult, &adult_fare, number_of_passengers.adult - free_passengers.adult),
+        (PassengerCategory::Adult, &free_fare, free_passengers.adult),
+        (PassengerCategory::Child, &child_fare, number_of_passengers.child - free_passengers.child),
+        (PassengerCategory::Child, &free_fare, free_passengers.child),
+    ];
+
+    let directions = match fare_query.reserve_type {
+        ReserveType::SingleTrip => vec![Direction::Outbound],
+        ReserveType::RoundTrip => vec![Direction::Outbound, Direction::Return],
+    };
+
+    // 往復でも 1 方向ごとに乗車券を分けて発券するため、有効期間は片道の乗車券として求める
+    let validity_period = calc_validity_period(&fare_query.ride_section.get_fare_kilometer());
+
+    let mut tickets = vec![];
+    for direction in directions {
+        let ride_section = match direction {
+            Direction::Outbound => fare_query.ride_section,
+            Direction::Return => {
+                let ride_section = &fare_query.ride_section;
+                RideSection {
+                    departure: ride_section.arrival,
+                    arrival: ride_section.departure,
+                    departure_connection: ride_section.arrival_connection,
+                    arrival_connection: ride_section.departure_connection,
+                }
+            }
+        };
+        for (passenger_category, (train_fare, express_fare), count) in &passengers {
+            for _ in 0..*count {
+                tickets.push(Ticket {
+                    ticket_type: TicketType::TrainTicket,
+                    passenger_category: *passenger_category,
+                    direction,
+                    ride_section,
+                    city_zones: ride_section.get_city_zones(),
+                    departure_date: fare_query.departure_date,
+                    train: None,
+                    seat_type: None,
+                    validity_period: Some(validity_period),
+                    price: train_fare.value.clone(),
+                });
+                tickets.push(Ticket {
+                    ticket_type: TicketType::ExpressTicket,
+                    passenger_category: *passenger_category,
+                    direction,
+                    ride_section: RideSection { departure_connection: None, arrival_connection: None, ..ride_section },
+                    city_zones: (None, None),
+                    departure_date: fare_query.departure_date,
+                    train: Some(fare_query.train),
+                    seat_type: Some(fare_query.seat_type),
+                    validity_period: None,
+                    price: express_fare.value.clone(),
+                });
+            }
+        }
+    }
+    tickets
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    use crate::domain::base::city_zone::CityZone::TokyoWards;
+    use crate::domain::base::departure_date::DepartureDate;
+    use crate::domain::base::fare_query::FareQuery;
+    use crate::domain::base::number_of_passengers::NumberOfPassengers;
+    use crate::domain::base::reserve_type::ReserveType;
+    use crate::domain::base::reserve_type::ReserveType::*;
+    use crate::domain::base::ride_section::RideSection;
+    use crate::domain::base::ride_section::Station::*;
+    use crate::domain::base::seat_type::SeatType::*;
+    use crate::domain::base::train::Train::*;
+    use crate::domain::discount::discount_policy::DiscountPolicy;
+    use crate::domain::discount::discount_rule::DiscountRuleRegistry;
+    use crate::domain::discount::individual_discount::judge_individual_discounts;
+    use crate::domain::discount::overall_discount::{judge_overall_discount, GroupFreePassengerPolicy};
+    use crate::domain::fare::single_trip_adult_fare::calc_single_trip_adult_fare;
+    use crate::domain::fare::total_fare::calc_total_fare;
+    use crate::domain::ticket::ticket_issuance::Direction::*;
+    use crate::domain::ticket::ticket_issuance::PassengerCategory::*;
+    use crate::domain::ticket::ticket_issuance::TicketType::*;
+    use crate::domain::ticket::ticket_issuance::{issue_tickets, Ticket};
+    use crate::domain::ticket::validity_period::ValidityPeriod;
+    use crate::fundamental::amount::Amount;
+
+    fn fare_query(reserve_type: ReserveType, adult: usize, child: usize) -> FareQuery {
+        FareQuery {
+            ride_section: RideSection {
+                departure: Tokyo,
+                arrival: Himeji,
+                departure_connection: None,
+                arrival_connection: None,
+            },
+            reserve_type,
+            departure_date: DepartureDate { value: NaiveDate::from_ymd_opt(2024, 12, 28).unwrap() },
+            number_of_passengers: NumberOfPassengers { adult, child },
+            ..FareQuery::default()
+        }
+    }
+
+    #[rstest]
+    #[case(SingleTrip, 1, 0, 2)]
+    #[case(SingleTrip, 1, 1, 4)]
+    #[case(RoundTrip, 2, 1, 12)]
+    #[case(RoundTrip, 10, 5, 60)]
+    #[case(RoundTrip, 40, 20, 240)]
+    #[case(SingleTrip, 100, 0, 200)]
+    #[case(SingleTrip, 0, 31, 62)]
+    #[case(RoundTrip, 1, 99, 400)]
+    fn sum_equals_total_fare(
+        #[case] reserve_type: ReserveType,
+        #[case] adult: usize,
+        #[case] child: usize,
+        #[case] ticket_count: usize,
+    ) {
+        let fare_query = fare_query(reserve_type, adult, child);
+        let single_trip_adult_fare = calc_single_trip_adult_fare(
+            &fare_query.ride_section,
+            &fare_query.train,
+            &fare_query.seat_type,
+            &fare_query.departure_date,
+        )
+        .unwrap();
+        let overall_discount = judge_overall_discount(
+            &fare_query.group_type,
+            &fare_query.number_of_passengers,
+            &GroupFreePassengerPolicy::default(),
+        );
+        let individual_discounts = judge_individual_discounts(&fare_query, &DiscountRuleRegistry::default());
+
+        let tickets = issue_tickets(
+            &fare_query,
+            &single_trip_adult_fare,
+            &overall_discount,
+            &individual_discounts,
+            &DiscountPolicy::default(),
+        );
+
+        assert_eq!(ticket_count, tickets.len());
+        assert_eq!(
+            calc_total_fare(
+                &fare_query.reserve_type,
+                &fare_query.number_of_passengers,
+                &single_trip_adult_fare,
+                &overall_discount,
+                &individual_discounts,
+                &DiscountPolicy::default(),
+            ),
+            Amount { value: tickets.iter().map(|ticket| ticket.price.value).sum() }
+        );
+    }
+
+    #[test]
+    fn round_trip_tickets() {
+        let fare_query = fare_query(RoundTrip, 1, 0);
+        let single_trip_adult_fare = calc_single_trip_adult_fare(
+            &fare_query.ride_section,
+            &fare_query.train,
+            &fare_query.seat_type,
+            &fare_query.departure_date,
+        )
+        .unwrap();
+
+        let tickets = issue_tickets(&fare_query, &single_trip_adult_fare, &None, &[], &DiscountPolicy::default());
+
+        let ticket =
+            |ticket_type, direction, ride_section, city_zones, train, seat_type, validity_period, price| Ticket {
+                ticket_type,
+                passenger_category: Adult,
+                direction,
+                ride_section,
+                city_zones,
+                departure_date: fare_query.departure_date,
+                train,
+                seat_type,
+                validity_period,
+                price: Amount { value: price },
+            };
+        let validity_period = ValidityPeriod { days: 5, stopover: true };
+        let outbound =
+            RideSection { departure: Tokyo, arrival: Himeji, departure_connection: None, arrival_connection: None };
+        let r#return =
+            RideSection { departure: Himeji, arrival: Tokyo, departure_connection: None, arrival_connection: None };
+        assert_eq!(
+            vec![
+                ticket(
+                    TrainTicket,
+                    Outbound,
+                    outbound,
+                    (Some(TokyoWards), None),
+                    None,
+                    None,
+                    Some(validity_period),
+                    10010
+                ),
+                ticket(ExpressTicket, Outbound, outbound, (None, None), Some(Nozomi), Some(Reserved), None, 6650),
+                ticket(
+                    TrainTicket,
+                    Return,
+                    r#return,
+                    (None, Some(TokyoWards)),
+                    None,
+                    None,
+                    Some(validity_period),
+                    10010
+                ),
+                ticket(ExpressTicket, Return, r#return, (None, None), Some(Nozomi), Some(Reserved), None, 6650),
+            ],
+            tickets
+        );
+    }
+}
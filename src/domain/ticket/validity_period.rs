@@ -0,0 +1,38 @@
+use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+// 乗車券の有効期間 ( 日数 ) と途中下車の可否
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct ValidityPeriod {
+    pub days: u32,
+    pub stopover: bool,
+}
+
+// 100 km までは当日限り、 200 km までは 2 日、以降 200 km ごとに 1 日を加える
+// 途中下車は 100 km を超える場合に限り認める
+// 往復でも乗車券は 1 方向ごとに発券するため、片道の乗車券の有効期間だけを扱う ( 往復乗車券の 2 倍の有効期間は扱わない )
+pub fn calc_validity_period(operating_kilometer: &OperatingKilometer) -> ValidityPeriod {
+    let km = operating_kilometer.value;
+    let days = if km <= 100.0 { 1 } else { 2 + ((km - 200.0).max(0.0) / 200.0).ceil() as u32 };
+    ValidityPeriod { days, stopover: 100.0 < km }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::domain::ticket::validity_period::{calc_validity_period, ValidityPeriod};
+    use crate::fundamental::operating_kilometer::OperatingKilometer;
+
+    #[rstest]
+    #[case(50.0, 1, false)]
+    #[case(100.0, 1, false)]
+    #[case(100.1, 2, true)]
+    #[case(200.0, 2, true)]
+    #[case(201.0, 3, true)]
+    #[case(400.0, 3, true)]
+    #[case(553.0, 4, true)]
+    #[case(644.0, 5, true)]
+    fn test_calc_validity_period(#[case] km: f64, #[case] days: u32, #[case] stopover: bool) {
+        assert_eq!(ValidityPeriod { days, stopover }, calc_validity_period(&OperatingKilometer { value: km }));
+    }
+}
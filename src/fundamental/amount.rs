@@ -1,5 +1,10 @@
 use std::ops::{Add, Mul, Sub};
 
+use anyhow::bail;
+
+use crate::fundamental::fraction::Fraction;
+use crate::fundamental::rounding::Rounding;
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 pub struct Amount {
     pub value: u64,
@@ -21,12 +26,41 @@ impl Sub<Amount> for Amount {
     }
 }
 
-impl Mul<f32> for Amount {
+impl Mul<usize> for Amount {
     type Output = Amount;
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        let value = (self.value as f32 * rhs) as u64;
-        Amount { value: value / 10 * 10 }
+    fn mul(self, rhs: usize) -> Self::Output {
+        Amount { value: self.value * rhs as u64 }
+    }
+}
+
+impl Amount {
+    // 割合を掛けると端数が出るため、どの端数処理に従うかを呼び出し側で指定する
+    pub fn multiply(&self, rate: &Fraction, rounding: &Rounding) -> Amount {
+        Amount { value: rounding.round(&(Fraction::integer(self.value) * *rate)) }
+    }
+
+    // 重みの比で按分する ( 1 円未満を切り捨て、端数は重みが最も大きいものに寄せる )
+    // 按分した金額の合計は元の金額に一致する
+    // 按分先がない場合や重みの合計が 0 の場合は比が定まらないため受け付けない
+    pub fn apportion(&self, weights: &[u64]) -> anyhow::Result<Vec<Amount>> {
+        let total = weights.iter().sum::<u64>();
+        if total == 0 {
+            bail!("按分する重みの合計が 0 です");
+        }
+        let mut amounts = weights
+            .iter()
+            .map(|weight| Amount {
+                value: Rounding::FLOOR_TO_ONE.round(&(Fraction::integer(self.value) * Fraction::new(*weight, total))),
+            })
+            .collect::<Vec<_>>();
+        let remainder = self.value - amounts.iter().map(|amount| amount.value).sum::<u64>();
+        if let Some(index) =
+            weights.iter().enumerate().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0))).map(|(index, _)| index)
+        {
+            amounts[index].value += remainder;
+        }
+        Ok(amounts)
     }
 }
 
@@ -35,6 +69,10 @@ mod tests {
     use rstest::rstest;
 
     use crate::fundamental::amount::Amount;
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::rounding::RoundingMode::*;
+    use crate::fundamental::rounding::RoundingUnit::*;
+    use crate::fundamental::rounding::{Rounding, RoundingMode, RoundingUnit};
 
     #[rstest]
     #[case(150, 20, 170)]
@@ -53,13 +91,51 @@ mod tests {
     }
 
     #[rstest]
-    #[case(150, 2.0, 300)]
-    #[case(160, 0.5, 80)]
-    #[case(150, 0.5, 70)]
-    #[case(10, 0.5, 0)]
-    #[case(10010, 0.9, 9000)]
-    fn mul(#[case] lhs: u64, #[case] rhs: f32, #[case] exp: u64) {
+    #[case(150, 2, 300)]
+    #[case(150, 0, 0)]
+    fn mul(#[case] lhs: u64, #[case] rhs: usize, #[case] exp: u64) {
         let amount = Amount { value: lhs };
         assert_eq!(Amount { value: exp }, amount * rhs);
     }
+
+    #[rstest]
+    #[case(150, 200, Floor, Ten, 300)]
+    #[case(160, 50, Floor, Ten, 80)]
+    #[case(150, 50, Floor, Ten, 70)]
+    #[case(10, 50, Floor, Ten, 0)]
+    #[case(10010, 90, Floor, Ten, 9000)]
+    #[case(10010, 90, Floor, One, 9009)]
+    #[case(10010, 90, Ceil, Hundred, 9100)]
+    #[case(150, 50, Round, Ten, 80)]
+    #[case(10010, 85, Ceil, Ten, 8510)]
+    fn multiply(
+        #[case] lhs: u64,
+        #[case] percent: u64,
+        #[case] mode: RoundingMode,
+        #[case] unit: RoundingUnit,
+        #[case] exp: u64,
+    ) {
+        let amount = Amount { value: lhs };
+        assert_eq!(Amount { value: exp }, amount.multiply(&Fraction::percent(percent), &Rounding { mode, unit }));
+    }
+
+    #[rstest]
+    #[case(6650, vec![5530, 910], vec![5711, 939])]
+    #[case(100, vec![1, 1, 1], vec![34, 33, 33])]
+    #[case(5230, vec![2889], vec![5230])]
+    #[case(0, vec![5530, 910], vec![0, 0])]
+    #[case(100, vec![0, 1], vec![0, 100])]
+    fn apportion(#[case] value: u64, #[case] weights: Vec<u64>, #[case] exp: Vec<u64>) {
+        let exp = exp.into_iter().map(|value| Amount { value }).collect::<Vec<_>>();
+        assert_eq!(exp, Amount { value }.apportion(&weights).unwrap());
+    }
+
+    // 按分先がない場合や重みがすべて 0 の場合は金額が失われるため受け付けない
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![0, 0])]
+    fn apportion_without_weights(#[case] weights: Vec<u64>) {
+        let act = Amount { value: 100 }.apportion(&weights);
+        assert_eq!("按分する重みの合計が 0 です", act.unwrap_err().to_string());
+    }
 }
@@ -0,0 +1,143 @@
+use std::fmt;
+use std::ops::{Add, Mul};
+
+// 割合や端数処理前の金額を誤差なく扱うための分数 ( 常に既約分数で持つ )
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Fraction {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl Fraction {
+    pub const ONE: Fraction = Fraction { numerator: 1, denominator: 1 };
+
+    pub fn new(numerator: u64, denominator: u64) -> Fraction {
+        assert_ne!(0, denominator, "denominator must not be zero");
+        reduce(numerator as u128, denominator as u128)
+    }
+
+    // 百分率 ( 90 は 0.9 )
+    pub fn percent(value: u64) -> Fraction {
+        Fraction::new(value, 100)
+    }
+
+    pub fn integer(value: u64) -> Fraction {
+        Fraction { numerator: value, denominator: 1 }
+    }
+
+    pub fn get_numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn get_denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    // 0 を下回る場合は 0 とする
+    pub fn saturating_sub(self, rhs: Fraction) -> Fraction {
+        let lhs = self.numerator as u128 * rhs.denominator as u128;
+        let rhs_numerator = rhs.numerator as u128 * self.denominator as u128;
+        if lhs <= rhs_numerator {
+            return Fraction::integer(0);
+        }
+        reduce(lhs - rhs_numerator, self.denominator as u128 * rhs.denominator as u128)
+    }
+}
+
+impl Add<Fraction> for Fraction {
+    type Output = Fraction;
+
+    fn add(self, rhs: Fraction) -> Self::Output {
+        reduce(
+            self.numerator as u128 * rhs.denominator as u128 + rhs.numerator as u128 * self.denominator as u128,
+            self.denominator as u128 * rhs.denominator as u128,
+        )
+    }
+}
+
+impl Mul<Fraction> for Fraction {
+    type Output = Fraction;
+
+    fn mul(self, rhs: Fraction) -> Self::Output {
+        reduce(self.numerator as u128 * rhs.numerator as u128, self.denominator as u128 * rhs.denominator as u128)
+    }
+}
+
+// 分母が 10 の累乗の約数であれば小数で、そうでなければ分数で表す
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(digits) = (0..=6).find(|digits| 10u64.pow(*digits) % self.denominator == 0) else {
+            return write!(f, "{}/{}", self.numerator, self.denominator);
+        };
+        let scale = 10u64.pow(digits);
+        let scaled = self.numerator * (scale / self.denominator);
+        let (integer, decimal) = (scaled / scale, scaled % scale);
+        if decimal == 0 {
+            return write!(f, "{integer}");
+        }
+        let decimal = format!("{decimal:0width$}", width = digits as usize);
+        write!(f, "{integer}.{}", decimal.trim_end_matches('0'))
+    }
+}
+
+fn reduce(numerator: u128, denominator: u128) -> Fraction {
+    let divisor = gcd(numerator, denominator);
+    Fraction {
+        numerator: u64::try_from(numerator / divisor).expect("fraction overflow"),
+        denominator: u64::try_from(denominator / divisor).expect("fraction overflow"),
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::fundamental::fraction::Fraction;
+
+    #[rstest]
+    #[case(Fraction::new(90, 100), Fraction::new(9, 10))]
+    #[case(Fraction::percent(85), Fraction::new(17, 20))]
+    #[case(Fraction::new(0, 7), Fraction::integer(0))]
+    fn new(#[case] act: Fraction, #[case] exp: Fraction) {
+        assert_eq!(exp, act);
+    }
+
+    #[rstest]
+    #[case(Fraction::percent(90), Fraction::percent(85), Fraction::new(153, 200))]
+    #[case(Fraction::integer(10010), Fraction::percent(90), Fraction::new(9009, 1))]
+    fn mul(#[case] lhs: Fraction, #[case] rhs: Fraction, #[case] exp: Fraction) {
+        assert_eq!(exp, lhs * rhs);
+    }
+
+    #[rstest]
+    #[case(Fraction::new(1, 2), Fraction::new(1, 3), Fraction::new(5, 6))]
+    fn add(#[case] lhs: Fraction, #[case] rhs: Fraction, #[case] exp: Fraction) {
+        assert_eq!(exp, lhs + rhs);
+    }
+
+    #[rstest]
+    #[case(Fraction::new(9, 2), Fraction::integer(1), Fraction::new(7, 2))]
+    #[case(Fraction::integer(1), Fraction::new(9, 2), Fraction::integer(0))]
+    #[case(Fraction::integer(3), Fraction::integer(3), Fraction::integer(0))]
+    fn saturating_sub(#[case] lhs: Fraction, #[case] rhs: Fraction, #[case] exp: Fraction) {
+        assert_eq!(exp, lhs.saturating_sub(rhs));
+    }
+
+    #[rstest]
+    #[case(Fraction::percent(90), "0.9")]
+    #[case(Fraction::percent(85), "0.85")]
+    #[case(Fraction::percent(90) * Fraction::percent(85), "0.765")]
+    #[case(Fraction::integer(2), "2")]
+    #[case(Fraction::new(1, 3), "1/3")]
+    fn display(#[case] sut: Fraction, #[case] exp: &str) {
+        assert_eq!(exp, sut.to_string());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod amount;
+pub mod fraction;
 pub mod operating_kilometer;
+pub mod rounding;
@@ -2,3 +2,10 @@
 pub struct OperatingKilometer {
     pub value: f64,
 }
+
+impl OperatingKilometer {
+    // 営業キロは 0.1 km 単位のため、比を誤差なく扱えるよう 0.1 km を 1 とする整数で返す
+    pub fn to_tenths(&self) -> u64 {
+        (self.value * 10.0).round() as u64
+    }
+}
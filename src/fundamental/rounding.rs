@@ -0,0 +1,97 @@
+use crate::fundamental::fraction::Fraction;
+
+// 端数の処理方法
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum RoundingMode {
+    // 切り捨て
+    Floor,
+    // 四捨五入
+    Round,
+    // 切り上げ
+    Ceil,
+}
+
+// 端数を処理する単位
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum RoundingUnit {
+    One,
+    Ten,
+    Hundred,
+}
+
+impl RoundingUnit {
+    pub fn get_value(&self) -> u64 {
+        match self {
+            Self::One => 1,
+            Self::Ten => 10,
+            Self::Hundred => 100,
+        }
+    }
+}
+
+// 割合を掛けた金額の端数処理 ( 10 円未満切り捨てなど )
+// 運賃と料金ごと、割引ごとにどの端数処理に従うかを明示する
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct Rounding {
+    pub mode: RoundingMode,
+    pub unit: RoundingUnit,
+}
+
+impl Rounding {
+    // JR の運賃と料金の多くは 10 円未満を切り捨てる
+    pub const FLOOR_TO_TEN: Rounding = Rounding { mode: RoundingMode::Floor, unit: RoundingUnit::Ten };
+
+    // 会社ごとの按分は 1 円未満を切り捨てる
+    pub const FLOOR_TO_ONE: Rounding = Rounding { mode: RoundingMode::Floor, unit: RoundingUnit::One };
+
+    // 整数の割り算で端数を処理する ( 四捨五入はちょうど半分を切り上げる )
+    pub fn round(&self, value: &Fraction) -> u64 {
+        let unit = self.unit.get_value() as u128;
+        let divisor = value.get_denominator() as u128 * unit;
+        let numerator = value.get_numerator() as u128;
+        let (quotient, remainder) = (numerator / divisor, numerator % divisor);
+        let units = match self.mode {
+            RoundingMode::Floor => quotient,
+            RoundingMode::Round if divisor <= remainder * 2 => quotient + 1,
+            RoundingMode::Round => quotient,
+            RoundingMode::Ceil if 0 < remainder => quotient + 1,
+            RoundingMode::Ceil => quotient,
+        };
+        (units * unit) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::fundamental::fraction::Fraction;
+    use crate::fundamental::rounding::RoundingMode::*;
+    use crate::fundamental::rounding::RoundingUnit::*;
+    use crate::fundamental::rounding::{Rounding, RoundingMode, RoundingUnit};
+
+    #[rstest]
+    #[case(Floor, One, 90099, 10, 9009)]
+    #[case(Floor, Ten, 9009, 1, 9000)]
+    #[case(Floor, Hundred, 9099, 1, 9000)]
+    #[case(Round, One, 44525, 10, 4453)]
+    #[case(Round, Ten, 4455, 1, 4460)]
+    #[case(Round, Ten, 44549, 10, 4450)]
+    #[case(Round, Hundred, 4450, 1, 4500)]
+    #[case(Ceil, One, 90081, 10, 9009)]
+    #[case(Ceil, Ten, 9001, 1, 9010)]
+    #[case(Ceil, Ten, 9000, 1, 9000)]
+    #[case(Ceil, Hundred, 9001, 1, 9100)]
+    #[case(Floor, Ten, 0, 1, 0)]
+    // 0.9 と 0.85 を掛けた 10,010 x 0.765 => 7,657.65 も誤差なく切り捨てる
+    #[case(Floor, Ten, 765765, 100, 7650)]
+    fn round(
+        #[case] mode: RoundingMode,
+        #[case] unit: RoundingUnit,
+        #[case] numerator: u64,
+        #[case] denominator: u64,
+        #[case] exp: u64,
+    ) {
+        assert_eq!(exp, Rounding { mode, unit }.round(&Fraction::new(numerator, denominator)));
+    }
+}
@@ -1,37 +1,313 @@
-use crate::application::{ReserveTypeInput, SeatTypeInput, StationInput, TrainInput};
+use crate::application::{
+    ConventionalStationInput, DiscountPolicyInput, DiscountTargetInput, ExProductInput, FareQueryInput, GroupTypeInput,
+    IssuedFareInput, PaymentMediumInput, PromotionBenefitInput, PromotionInput, ReservationInput, ReserveTypeInput,
+    RoundingModeInput, RoundingPointInput, RoundingUnitInput, SeatReleaseInput, SeatTypeInput, SettlementOutput,
+    SingleFareRoundingInput, StackingModeInput, StackingOverrideInput, StationInput, TrainInput,
+};
 use itertools::Itertools;
+use std::env;
+use std::process::ExitCode;
 
 mod application;
 mod domain;
 mod fundamental;
 
-fn main() {
-    presentation();
+fn main() -> ExitCode {
+    let args = env::args().collect_vec();
+    match args.get(1).map(String::as_str) {
+        Some("tickets") => tickets(),
+        Some("refund") => {
+            refund();
+            ExitCode::SUCCESS
+        }
+        Some("change") => change(),
+        Some("trains") => trains(args.get(2).map(String::as_str).unwrap_or("timetable/timetable.csv")),
+        Some("availability") => availability(args.get(2).map(String::as_str).unwrap_or("inventory/inventory.csv")),
+        Some("seats") => seats(args.get(2).map(String::as_str).unwrap_or("inventory/inventory.csv")),
+        Some("release") => release(args.get(2).map(String::as_str).unwrap_or("inventory/inventory.csv")),
+        Some("revenue") => revenue(),
+        Some("validate-tariff") => validate_tariff(args.get(2).map(String::as_str).unwrap_or("tariff/tariff.csv")),
+        _ => presentation(),
+    }
 }
 
-fn presentation() {
-    let amount = application::invoke(
-        validate_departure(),
-        validate_arrival(),
-        validate_train(),
-        validate_seat_type(),
-        validate_reserve_type(),
+fn tickets() -> ExitCode {
+    let (tickets, promotion_usages) =
+        match application::issue(validate_fare_query(), validate_discount_policy(), validate_promotions()) {
+            Ok(issued) => issued,
+            Err(error) => {
+                println!("{error:#}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+    for ticket in tickets {
+        let train = match (ticket.train, ticket.seat_type) {
+            (Some(train), Some(seat_type)) => format!(" {train} {seat_type}"),
+            _ => String::new(),
+        };
+        let validity = match (ticket.validity_days, ticket.stopover) {
+            (Some(days), Some(true)) => format!(" 有効 {days} 日 途中下車可"),
+            (Some(days), _) => format!(" 有効 {days} 日"),
+            _ => String::new(),
+        };
+        println!(
+            "{} {} {} {} → {} {}{train}{validity} {} 円",
+            ticket.ticket_type,
+            ticket.passenger_category,
+            ticket.direction,
+            ticket.departure,
+            ticket.arrival,
+            ticket.departure_date,
+            format_amount(ticket.price)
+        );
+    }
+    for promotion_usage in promotion_usages {
+        println!(
+            "プロモーション {}: {} / {} 回利用済み",
+            promotion_usage.code, promotion_usage.used_count, promotion_usage.max_uses
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn refund() {
+    let (cancellation_y, cancellation_m, cancellation_d) = validate_cancellation_date();
+    let refund = application::refund(
+        validate_issued_fares(),
         validate_y(),
         validate_m(),
         validate_d(),
-        validate_adult(),
-        validate_child(),
+        cancellation_y,
+        cancellation_m,
+        cancellation_d,
     );
 
-    let result = format!("{amount:0}")
+    match refund {
+        Some((passengers, total)) => {
+            for (i, passenger) in passengers.iter().enumerate() {
+                println!(
+                    "{} 人目: 手数料 {} 円 / 払戻額 {} 円",
+                    i + 1,
+                    format_amount(passenger.handling_fee),
+                    format_amount(passenger.refundable_amount)
+                );
+            }
+            println!(
+                "合計　: 手数料 {} 円 / 払戻額 {} 円",
+                format_amount(total.handling_fee),
+                format_amount(total.refundable_amount)
+            );
+        }
+        None => println!("出発日を過ぎているため払い戻せません"),
+    }
+}
+
+fn change() -> ExitCode {
+    let (change_y, change_m, change_d) = validate_change_date();
+    let change = application::change(
+        validate_current_reservation(),
+        validate_requested_reservation(),
+        validate_previous_change_count(),
+        change_y,
+        change_m,
+        change_d,
+    );
+
+    match change {
+        Ok(Some(change)) => {
+            println!("手数料: {} 円", format_amount(change.handling_fee));
+            match change.settlement {
+                SettlementOutput::Payment(amount) => println!("追加支払: {} 円", format_amount(amount)),
+                SettlementOutput::Refund(amount) => println!("差額返金: {} 円", format_amount(amount)),
+                SettlementOutput::Even => println!("差額はありません"),
+            }
+        }
+        Ok(None) => println!("出発日を過ぎているため変更できません"),
+        Err(error) => {
+            println!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn format_amount(amount: u64) -> String {
+    // 下の桁から 3 桁ずつ区切る
+    format!("{amount:0}")
         .chars()
+        .rev()
         .collect_vec()
         .chunks(3)
-        .map(|chunk| chunk.iter().collect::<String>())
-        .collect_vec()
-        .join(",");
+        .map(|chunk| chunk.iter().rev().collect::<String>())
+        .rev()
+        .join(",")
+}
 
-    println!("総料金は {result:0} 円です");
+fn trains(path: &str) -> ExitCode {
+    let (hour, minute) = validate_earliest_departure_time();
+    let runs = application::search_trains(
+        path,
+        validate_fare_query(),
+        validate_discount_policy(),
+        validate_promotions(),
+        hour,
+        minute,
+    );
+
+    match runs {
+        Ok(runs) => {
+            for run in &runs {
+                let total_fare = match &run.total_fare {
+                    Ok(total_fare) => format!("総料金 {} 円", format_amount(*total_fare)),
+                    Err(reason) => reason.clone(),
+                };
+                println!("{} {} 号 {} → {} {total_fare}", run.train, run.number, run.departure_time, run.arrival_time);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{path}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn availability(path: &str) -> ExitCode {
+    match application::propose_seats(path, validate_fare_query()) {
+        Ok(proposals) => {
+            for proposal in &proposals {
+                println!(
+                    "{} {} 号 ( {} ) 空席 {} 席に {} 名",
+                    proposal.train, proposal.number, proposal.departure_date, proposal.available_count, proposal.count
+                );
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{path}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn seats(path: &str) -> ExitCode {
+    match application::reserve_seats(path, validate_fare_query()) {
+        Ok(reservations) => {
+            for reservation in &reservations {
+                println!(
+                    "{} {} 号 ( {} ) 指定席 {} 席中 {} 席: {}",
+                    reservation.train,
+                    reservation.number,
+                    reservation.departure_date,
+                    reservation.reserved_capacity,
+                    reservation.seats.len(),
+                    reservation.seats.join(", ")
+                );
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{path}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn release(path: &str) -> ExitCode {
+    match application::release_seats(path, validate_seat_release()) {
+        Ok(release) => {
+            println!(
+                "{} {} 号 ( {} ) 指定席 {} 席中 {} 席を空席に戻しました: {}",
+                release.train,
+                release.number,
+                release.departure_date,
+                release.reserved_capacity,
+                release.seats.len(),
+                release.seats.join(", ")
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{path}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn revenue() -> ExitCode {
+    let operator_total_fares = match application::apportion_revenues(
+        validate_revenue_queries(),
+        validate_discount_policy(),
+        validate_promotions(),
+    ) {
+        Ok(operator_total_fares) => operator_total_fares,
+        Err(error) => {
+            println!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for operator_total_fare in &operator_total_fares {
+        println!("{}: 総料金 {} 円", operator_total_fare.operator, format_amount(operator_total_fare.total_fare));
+    }
+    ExitCode::SUCCESS
+}
+
+fn validate_tariff(path: &str) -> ExitCode {
+    match application::validate_tariff(path) {
+        Ok(violations) if violations.is_empty() => {
+            println!("{path}: 問題はありません");
+            ExitCode::SUCCESS
+        }
+        Ok(violations) => {
+            for violation in &violations {
+                match violation.line_number {
+                    Some(line_number) => println!("{path}:{line_number}: {}", violation.message),
+                    None => println!("{path}: {}", violation.message),
+                }
+            }
+            println!("{} 件の問題があります", violations.len());
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            println!("{path}: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn presentation() -> ExitCode {
+    let quote = match application::invoke(validate_fare_query(), validate_discount_policy(), validate_promotions()) {
+        Ok(quote) => quote,
+        Err(error) => {
+            println!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let special_train_fare = if quote.special_train_fare { " 特定区間運賃" } else { "" };
+    println!("運賃　　: {} 円 ( {}{special_train_fare} )", format_amount(quote.train_fare), quote.train_fare_type);
+    let special_express_fare = if quote.special_express_fare { " ( 特定特急料金 )" } else { "" };
+    println!("特急料金: {} 円{special_express_fare}", format_amount(quote.express_fare));
+    let stopover = if quote.stopover { " 途中下車可" } else { "" };
+    println!("有効期間: 乗車券 {} 日{stopover}", quote.validity_days);
+    for operator_revenue in &quote.operator_revenues {
+        println!(
+            "会社別　: {} 運賃 {} 円 特急料金 {} 円 総料金 {} 円",
+            operator_revenue.operator,
+            format_amount(operator_revenue.train_fare),
+            format_amount(operator_revenue.express_fare),
+            format_amount(operator_revenue.total_fare)
+        );
+    }
+    for discount in &quote.discounts {
+        println!("割引　　: {discount}");
+    }
+    for note in &quote.notes {
+        println!("備考　　: {note}");
+    }
+    println!("総料金は {} 円です", format_amount(quote.total_fare));
     // 運賃　　　　　: 10,010 * 往復割引 ( 0.9 ) => 9,000
     // 特急　　　　　: 6,450 + 繁忙期 ( 200 ) => 6,650
     // 片道おとな料金: 15,650
@@ -40,6 +316,70 @@ fn presentation() {
     // こども人数　　: 20
     // 往復　　　　　: x2
     // 総料金　　　　: (15,650 x 39) x 2 + (7,820 x 20) x 2 => 1,533,500
+    ExitCode::SUCCESS
+}
+
+fn validate_fare_query() -> FareQueryInput {
+    FareQueryInput {
+        departure: validate_departure(),
+        arrival: validate_arrival(),
+        train: validate_train(),
+        seat_type: validate_seat_type(),
+        reserve_type: validate_reserve_type(),
+        y: validate_y(),
+        m: validate_m(),
+        d: validate_d(),
+        adult: validate_adult(),
+        child: validate_child(),
+        group_type: validate_group_type(),
+        payment_medium: validate_payment_medium(),
+        ex_product: validate_ex_product(),
+        departure_connection: validate_departure_connection(),
+        arrival_connection: validate_arrival_connection(),
+        booking_y: validate_booking_y(),
+        booking_m: validate_booking_m(),
+        booking_d: validate_booking_d(),
+        promotion_code: validate_promotion_code(),
+    }
+}
+
+fn validate_revenue_queries() -> Vec<FareQueryInput> {
+    vec![
+        validate_fare_query(),
+        FareQueryInput {
+            departure: StationInput::ShinOsaka,
+            arrival: StationInput::KagoshimaChuo,
+            train: TrainInput::Mizuho,
+            reserve_type: ReserveTypeInput::SingleTrip,
+            adult: 2,
+            child: 0,
+            ..validate_fare_query()
+        },
+        FareQueryInput {
+            arrival: StationInput::Hakata,
+            reserve_type: ReserveTypeInput::SingleTrip,
+            adult: 1,
+            child: 1,
+            ..validate_fare_query()
+        },
+    ]
+}
+
+fn validate_discount_policy() -> DiscountPolicyInput {
+    DiscountPolicyInput {
+        stacking_overrides: vec![StackingOverrideInput {
+            name: "団体割引".to_string(),
+            stacking_mode: StackingModeInput::Multiplicative,
+            rounding_point: RoundingPointInput::Last,
+        }],
+        single_fare_rounding: SingleFareRoundingInput::PerComponent,
+        group_min_passengers: 31,
+        passengers_per_free_passenger: 50,
+    }
+}
+
+fn validate_earliest_departure_time() -> (u32, u32) {
+    (8, 0)
 }
 
 fn validate_departure() -> StationInput {
@@ -50,6 +390,14 @@ fn validate_arrival() -> StationInput {
     StationInput::Himeji
 }
 
+fn validate_departure_connection() -> Option<ConventionalStationInput> {
+    None
+}
+
+fn validate_arrival_connection() -> Option<ConventionalStationInput> {
+    None
+}
+
 fn validate_train() -> TrainInput {
     TrainInput::Nozomi
 }
@@ -81,3 +429,109 @@ fn validate_adult() -> usize {
 fn validate_child() -> usize {
     20
 }
+
+fn validate_group_type() -> GroupTypeInput {
+    GroupTypeInput::Ordinary
+}
+
+fn validate_payment_medium() -> PaymentMediumInput {
+    PaymentMediumInput::Ticket
+}
+
+fn validate_ex_product() -> Option<ExProductInput> {
+    None
+}
+
+fn validate_booking_y() -> i32 {
+    2024
+}
+
+fn validate_booking_m() -> u32 {
+    11
+}
+
+fn validate_booking_d() -> u32 {
+    1
+}
+
+fn validate_promotion_code() -> Option<String> {
+    Some("EXHAYA".to_string())
+}
+
+fn validate_promotions() -> Vec<PromotionInput> {
+    vec![PromotionInput {
+        code: "EXHAYA".to_string(),
+        name: "EX早特".to_string(),
+        booking_from: (2024, 1, 1),
+        booking_to: (2024, 12, 31),
+        travel_from: (2024, 1, 1),
+        travel_to: (2024, 12, 24),
+        station_pairs: vec![(StationInput::Tokyo, StationInput::ShinOsaka)],
+        trains: vec![TrainInput::Nozomi],
+        seat_types: vec![SeatTypeInput::Reserved],
+        target: DiscountTargetInput::ExpressFare,
+        benefit: PromotionBenefitInput::FixedAmount(1000),
+        rounding_mode: RoundingModeInput::Floor,
+        rounding_unit: RoundingUnitInput::Ten,
+        stacking_mode: StackingModeInput::Sequential,
+        rounding_point: RoundingPointInput::EachStep,
+        max_uses: 100,
+        used_count: 0,
+    }]
+}
+
+fn validate_seat_release() -> SeatReleaseInput {
+    SeatReleaseInput {
+        train: TrainInput::Nozomi,
+        number: 1,
+        y: validate_y(),
+        m: validate_m(),
+        d: validate_d(),
+        seats: vec![(1, 1, 'A'), (1, 1, 'B')],
+    }
+}
+
+fn validate_issued_fares() -> Vec<IssuedFareInput> {
+    vec![
+        IssuedFareInput { train_fare: 10010, express_fare: 6650, seat_type: SeatTypeInput::Reserved, ex_product: None },
+        IssuedFareInput { train_fare: 5000, express_fare: 3320, seat_type: SeatTypeInput::Reserved, ex_product: None },
+    ]
+}
+
+fn validate_cancellation_date() -> (i32, u32, u32) {
+    (2024, 12, 27)
+}
+
+fn validate_current_reservation() -> ReservationInput {
+    ReservationInput {
+        departure: StationInput::Tokyo,
+        arrival: StationInput::Himeji,
+        train: TrainInput::Hikari,
+        seat_type: SeatTypeInput::Reserved,
+        y: 2024,
+        m: 1,
+        d: 20,
+        ex_product: None,
+    }
+}
+
+fn validate_requested_reservation() -> ReservationInput {
+    ReservationInput {
+        departure: StationInput::Tokyo,
+        arrival: StationInput::Himeji,
+        train: TrainInput::Nozomi,
+        seat_type: SeatTypeInput::Reserved,
+        y: 2024,
+        m: 1,
+        d: 10,
+        ex_product: None,
+    }
+}
+
+fn validate_previous_change_count() -> usize {
+    0
+}
+
+fn validate_change_date() -> (i32, u32, u32) {
+    (2024, 1, 5)
+}